@@ -11,6 +11,11 @@ pub use cosmrs::crypto::{secp256k1::SigningKey, PublicKey};
 /// Represents a bech32 account identifier
 pub use cosmrs::AccountId;
 
+pub use self::{decode::*, vesting::*};
+
+pub mod decode;
+pub mod vesting;
+
 /// Represents a local account derived from a [`SigningKey`].
 ///
 /// Note: Attempting a transaction with an account made from a newly generated key will fail as the account does not actually exist
@@ -52,6 +57,14 @@ impl AccountInfo {
         Ok(self.public_key.account_id(prefix)?)
     }
 
+    /// The account's validator operator address, e.g. `cosmosvaloper1...` for the prefix `cosmos`
+    pub fn validator_address(&self, prefix: &str) -> Result<String, AccountError> {
+        Ok(self
+            .public_key
+            .account_id(&format!("{}valoper", prefix))?
+            .to_string())
+    }
+
     pub fn public_key(&self) -> PublicKey {
         self.public_key
     }
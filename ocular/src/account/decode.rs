@@ -0,0 +1,335 @@
+//! Decoding of on chain accounts by type. Besides the Cosmos SDK's account types, accounts of chain specific types
+//! can be decoded by registering an [`AccountDecoder`] for their type URL. Accounts of other types are decoded if
+//! their `BaseAccount` is where most account types keep it.
+use std::{collections::HashMap, str::FromStr};
+
+use cosmos_sdk_proto::cosmos::crypto::secp256k1;
+use prost::Message;
+
+use crate::{cosmos_modules::auth, error::AccountError, tx::Any};
+
+use super::{AccountId, BaseAccount, VestingAccount};
+
+pub const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+pub const MODULE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.ModuleAccount";
+/// Account type of Ethermint based chains such as Evmos
+pub const ETH_ACCOUNT_TYPE_URL: &str = "/ethermint.types.v1.EthAccount";
+
+const ETH_SECP256K1_PUBKEY_TYPE_URL: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// Extracts the [`BaseAccount`] from an encoded account of a custom type
+pub type AccountDecoder = fn(&Any) -> Result<BaseAccount, AccountError>;
+
+/// An account owned by a module, such as the distribution module's community pool
+#[derive(Clone, Debug)]
+pub struct ModuleAccount {
+    pub base_account: BaseAccount,
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+/// An Ethermint account, which may hold EVM contract code
+#[derive(Clone, Debug)]
+pub struct EthAccount {
+    /// Its `pub_key` is only set for a plain secp256k1 key
+    pub base_account: BaseAccount,
+    /// The account's key if it's an ethsecp256k1 key
+    pub pub_key: Option<EthPublicKey>,
+    pub code_hash: String,
+}
+
+/// An Ethermint `ethsecp256k1` public key. It has the same compressed secp256k1 encoding as a Cosmos key, but the
+/// account's address is derived from the keccak256 hash of the key like an Ethereum address, so it can't be used as
+/// a [`super::PublicKey`] to derive the address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthPublicKey {
+    pub key: Vec<u8>,
+}
+
+/// `ethermint.types.v1.EthAccount`
+#[derive(Clone, PartialEq, Message)]
+struct RawEthAccount {
+    #[prost(message, optional, tag = "1")]
+    pub base_account: Option<auth::BaseAccount>,
+    #[prost(string, tag = "2")]
+    pub code_hash: String,
+}
+
+/// Most account types, such as module, Ethermint and interchain accounts, hold their `BaseAccount` as field 1
+#[derive(Clone, PartialEq, Message)]
+struct RawAccountWithBase {
+    #[prost(message, optional, tag = "1")]
+    pub base_account: Option<auth::BaseAccount>,
+}
+
+/// Vesting account types hold their `BaseAccount` in a `BaseVestingAccount` at field 1
+#[derive(Clone, PartialEq, Message)]
+struct RawVestingAccountWithBase {
+    #[prost(message, optional, tag = "1")]
+    pub base_vesting_account: Option<RawAccountWithBase>,
+}
+
+/// An on chain account decoded according to its type URL
+#[derive(Clone, Debug)]
+pub enum Account {
+    Base(BaseAccount),
+    Module(ModuleAccount),
+    Vesting(VestingAccount),
+    Eth(EthAccount),
+    /// An account decoded by a registered [`AccountDecoder`], or of another type whose `BaseAccount` is in the
+    /// common position, such as an interchain account. `any` holds the full account for further decoding.
+    Custom {
+        base_account: BaseAccount,
+        any: Any,
+    },
+}
+
+impl Account {
+    pub fn base_account(&self) -> &BaseAccount {
+        match self {
+            Account::Base(account) => account,
+            Account::Module(account) => &account.base_account,
+            Account::Vesting(account) => &account.base_account,
+            Account::Eth(account) => &account.base_account,
+            Account::Custom { base_account, .. } => base_account,
+        }
+    }
+
+    pub fn into_base_account(self) -> BaseAccount {
+        match self {
+            Account::Base(account) => account,
+            Account::Module(account) => account.base_account,
+            Account::Vesting(account) => account.base_account,
+            Account::Eth(account) => account.base_account,
+            Account::Custom { base_account, .. } => base_account,
+        }
+    }
+}
+
+/// Decodes accounts of the Cosmos SDK's and Ethermint's types
+impl TryFrom<&Any> for Account {
+    type Error = AccountError;
+
+    fn try_from(any: &Any) -> Result<Account, Self::Error> {
+        AccountDecoders::default().decode(any)
+    }
+}
+
+/// Decoders for account types ocular doesn't support itself, keyed by type URL. Registered decoders take precedence
+/// over the built in ones.
+#[derive(Clone, Debug, Default)]
+pub struct AccountDecoders {
+    decoders: HashMap<String, AccountDecoder>,
+}
+
+impl AccountDecoders {
+    pub fn register(&mut self, type_url: &str, decoder: AccountDecoder) {
+        self.decoders.insert(type_url.to_string(), decoder);
+    }
+
+    pub fn decode(&self, any: &Any) -> Result<Account, AccountError> {
+        if let Some(decoder) = self.decoders.get(&any.type_url) {
+            return Ok(Account::Custom {
+                base_account: decoder(any)?,
+                any: any.clone(),
+            });
+        }
+
+        match any.type_url.as_str() {
+            BASE_ACCOUNT_TYPE_URL => {
+                Ok(Account::Base(decode::<auth::BaseAccount>(any)?.try_into()?))
+            }
+            MODULE_ACCOUNT_TYPE_URL => {
+                let account: auth::ModuleAccount = decode(any)?;
+
+                Ok(Account::Module(ModuleAccount {
+                    base_account: base_account(account.base_account)?,
+                    name: account.name,
+                    permissions: account.permissions,
+                }))
+            }
+            ETH_ACCOUNT_TYPE_URL => {
+                let account: RawEthAccount = decode(any)?;
+                let mut base = account
+                    .base_account
+                    .ok_or_else(|| AccountError::Empty("missing base account".to_string()))?;
+
+                // Addresses are derived from ethsecp256k1 keys differently, so they're kept apart from the base
+                // account's secp256k1 key
+                let pub_key = match base.pub_key.take() {
+                    Some(key) if key.type_url == ETH_SECP256K1_PUBKEY_TYPE_URL => {
+                        Some(EthPublicKey {
+                            key: decode::<secp256k1::PubKey>(&key)?.key,
+                        })
+                    }
+                    key => {
+                        base.pub_key = key;
+                        None
+                    }
+                };
+
+                Ok(Account::Eth(EthAccount {
+                    base_account: base.try_into()?,
+                    pub_key,
+                    code_hash: account.code_hash,
+                }))
+            }
+            type_url if VestingAccount::is_vesting_account(type_url) => {
+                Ok(Account::Vesting(VestingAccount::try_from(any)?))
+            }
+            _ => Ok(Account::Custom {
+                base_account: common_base_account(any)?,
+                any: any.clone(),
+            }),
+        }
+    }
+}
+
+/// Finds the `BaseAccount` of an account of an unknown type at field 1, or at field 1 of field 1 like in vesting
+/// accounts. Errors if neither holds a base account with a valid address.
+fn common_base_account(any: &Any) -> Result<BaseAccount, AccountError> {
+    let is_valid = |account: &auth::BaseAccount| AccountId::from_str(&account.address).is_ok();
+
+    if let Ok(RawAccountWithBase {
+        base_account: Some(account),
+    }) = decode(any)
+    {
+        if is_valid(&account) {
+            return account.try_into();
+        }
+    }
+
+    decode::<RawVestingAccountWithBase>(any)?
+        .base_vesting_account
+        .and_then(|a| a.base_account)
+        .filter(is_valid)
+        .ok_or_else(|| {
+            AccountError::UnsupportedType(format!("{} has no base account", any.type_url))
+        })?
+        .try_into()
+}
+
+fn decode<M: Message + Default>(any: &Any) -> Result<M, AccountError> {
+    M::decode(&any.value[..]).map_err(|e| AccountError::Decode(e.into()))
+}
+
+fn base_account(account: Option<auth::BaseAccount>) -> Result<BaseAccount, AccountError> {
+    account
+        .ok_or_else(|| AccountError::Empty("missing base account".to_string()))?
+        .try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::AccountInfo;
+
+    fn proto_base_account(pub_key: Option<Any>) -> auth::BaseAccount {
+        auth::BaseAccount {
+            address: "evmos1account".to_string(),
+            pub_key,
+            account_number: 12,
+            sequence: 4,
+        }
+    }
+
+    #[test]
+    fn decodes_accounts() {
+        let module = Any {
+            type_url: MODULE_ACCOUNT_TYPE_URL.to_string(),
+            value: auth::ModuleAccount {
+                base_account: Some(proto_base_account(None)),
+                name: "distribution".to_string(),
+                permissions: vec![],
+            }
+            .encode_to_vec(),
+        };
+        let key = AccountInfo::new("").public_key().to_bytes();
+        let eth = Any {
+            type_url: ETH_ACCOUNT_TYPE_URL.to_string(),
+            value: RawEthAccount {
+                base_account: Some(proto_base_account(Some(Any {
+                    type_url: ETH_SECP256K1_PUBKEY_TYPE_URL.to_string(),
+                    value: secp256k1::PubKey { key: key.clone() }.encode_to_vec(),
+                }))),
+                code_hash: "c5d2".to_string(),
+            }
+            .encode_to_vec(),
+        };
+
+        match Account::try_from(&module).unwrap() {
+            Account::Module(account) => assert_eq!(account.name, "distribution"),
+            account => panic!("unexpected account {:?}", account),
+        }
+
+        let account = Account::try_from(&eth).unwrap();
+
+        match &account {
+            Account::Eth(a) => {
+                assert_eq!(a.code_hash, "c5d2");
+                assert_eq!(a.pub_key, Some(EthPublicKey { key }));
+            }
+            account => panic!("unexpected account {:?}", account),
+        }
+        assert_eq!(account.base_account().sequence, 4);
+        assert!(account.into_base_account().pub_key.is_none());
+    }
+
+    #[test]
+    fn decodes_registered_account_types() {
+        let any = Any {
+            type_url: "/cosmos.auth.v1beta1.UnknownAccount".to_string(),
+            value: proto_base_account(None).encode_to_vec(),
+        };
+
+        assert!(Account::try_from(&any).is_err());
+
+        let mut decoders = AccountDecoders::default();
+        decoders.register(&any.type_url, |any| {
+            decode::<auth::BaseAccount>(any)?.try_into()
+        });
+
+        match decoders.decode(&any).unwrap() {
+            Account::Custom { base_account, any } => {
+                assert_eq!(base_account.account_number, 12);
+                assert_eq!(any.type_url, "/cosmos.auth.v1beta1.UnknownAccount");
+            }
+            account => panic!("unexpected account {:?}", account),
+        }
+    }
+
+    #[test]
+    fn decodes_unknown_account_types_with_common_layout() {
+        let address = AccountInfo::new("").address("cosmos").unwrap();
+        let base = auth::BaseAccount {
+            address: address.clone(),
+            ..proto_base_account(None)
+        };
+        let interchain = Any {
+            type_url: "/ibc.applications.interchain_accounts.v1.InterchainAccount".to_string(),
+            value: RawAccountWithBase {
+                base_account: Some(base.clone()),
+            }
+            .encode_to_vec(),
+        };
+        let clawback = Any {
+            type_url: "/evmos.vesting.v1.ClawbackVestingAccount".to_string(),
+            value: RawVestingAccountWithBase {
+                base_vesting_account: Some(RawAccountWithBase {
+                    base_account: Some(base),
+                }),
+            }
+            .encode_to_vec(),
+        };
+
+        for any in [interchain, clawback] {
+            match Account::try_from(&any).unwrap() {
+                Account::Custom { base_account, .. } => {
+                    assert_eq!(base_account.address, address);
+                    assert_eq!(base_account.account_number, 12);
+                }
+                account => panic!("unexpected account {:?}", account),
+            }
+        }
+    }
+}
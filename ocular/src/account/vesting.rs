@@ -0,0 +1,427 @@
+//! Vesting accounts and their schedules. Amounts vest at whole seconds the same way the Cosmos SDK's `x/auth/vesting`
+//! module computes them.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use prost::Message;
+
+use crate::{
+    cosmos_modules::vesting,
+    error::AccountError,
+    tx::{Any, Coin, Dec},
+};
+
+use super::BaseAccount;
+
+pub const CONTINUOUS_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.ContinuousVestingAccount";
+pub const DELAYED_VESTING_ACCOUNT_TYPE_URL: &str = "/cosmos.vesting.v1beta1.DelayedVestingAccount";
+pub const PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PeriodicVestingAccount";
+pub const PERMANENT_LOCKED_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PermanentLockedAccount";
+
+/// Scale of the SDK's 18 decimal `sdk.Dec`, which vesting ratios are rounded to
+const DEC_SCALE: u128 = 10u128.pow(Dec::DECIMALS);
+
+/// An amount that vests `length` seconds after the previous period ends
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VestingPeriod {
+    pub length: i64,
+    pub amount: Vec<Coin>,
+}
+
+/// How a vesting account's original vesting amount vests. Times are unix timestamps in seconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VestingSchedule {
+    /// Vests linearly between `start_time` and the account's end time
+    Continuous { start_time: i64 },
+    /// Vests all at once at the account's end time
+    Delayed,
+    /// Vests in periods starting at `start_time`
+    Periodic {
+        start_time: i64,
+        periods: Vec<VestingPeriod>,
+    },
+    /// Never vests
+    PermanentLocked,
+}
+
+#[derive(Clone, Debug)]
+pub struct VestingAccount {
+    pub base_account: BaseAccount,
+    pub original_vesting: Vec<Coin>,
+    pub delegated_free: Vec<Coin>,
+    pub delegated_vesting: Vec<Coin>,
+    pub end_time: i64,
+    pub schedule: VestingSchedule,
+}
+
+impl VestingAccount {
+    /// Returns true if `type_url` is one of the SDK's vesting account types
+    pub fn is_vesting_account(type_url: &str) -> bool {
+        [
+            CONTINUOUS_VESTING_ACCOUNT_TYPE_URL,
+            DELAYED_VESTING_ACCOUNT_TYPE_URL,
+            PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+            PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+        ]
+        .contains(&type_url)
+    }
+
+    /// Coins that have vested by `time`
+    pub fn vested_coins(&self, time: SystemTime) -> Vec<Coin> {
+        let time = unix_seconds(time);
+
+        match &self.schedule {
+            VestingSchedule::Continuous { start_time } => {
+                if time <= *start_time {
+                    Vec::new()
+                } else if time >= self.end_time {
+                    self.original_vesting.clone()
+                } else {
+                    let elapsed = (time - start_time) as u128;
+                    let duration = (self.end_time - start_time) as u128;
+                    let ratio = vested_ratio(elapsed, duration);
+
+                    self.original_vesting
+                        .iter()
+                        .map(|c| Coin::new(mul_ratio(c.amount, ratio, DEC_SCALE), &c.denom))
+                        .filter(|c| c.amount > 0)
+                        .collect()
+                }
+            }
+            VestingSchedule::Delayed => {
+                if time >= self.end_time {
+                    self.original_vesting.clone()
+                } else {
+                    Vec::new()
+                }
+            }
+            VestingSchedule::Periodic {
+                start_time,
+                periods,
+            } => {
+                if time >= self.end_time {
+                    return self.original_vesting.clone();
+                }
+
+                let mut period_end = *start_time;
+                let mut vested = Vec::new();
+
+                for period in periods {
+                    period_end += period.length;
+                    if time < period_end {
+                        break;
+                    }
+                    vested = add_coins(&vested, &period.amount);
+                }
+
+                vested
+            }
+            VestingSchedule::PermanentLocked => Vec::new(),
+        }
+    }
+
+    /// Coins that are still vesting at `time`
+    pub fn vesting_coins(&self, time: SystemTime) -> Vec<Coin> {
+        sub_coins(&self.original_vesting, &self.vested_coins(time))
+    }
+
+    /// Coins that can't be spent at `time`: vesting coins that aren't delegated
+    pub fn locked_coins(&self, time: SystemTime) -> Vec<Coin> {
+        sub_coins(&self.vesting_coins(time), &self.delegated_vesting)
+    }
+}
+
+impl TryFrom<&Any> for VestingAccount {
+    type Error = AccountError;
+
+    fn try_from(any: &Any) -> Result<VestingAccount, Self::Error> {
+        let (base_vesting_account, schedule) = match any.type_url.as_str() {
+            CONTINUOUS_VESTING_ACCOUNT_TYPE_URL => {
+                let account: vesting::ContinuousVestingAccount = decode(any)?;
+
+                (
+                    account.base_vesting_account,
+                    VestingSchedule::Continuous {
+                        start_time: account.start_time,
+                    },
+                )
+            }
+            DELAYED_VESTING_ACCOUNT_TYPE_URL => {
+                let account: vesting::DelayedVestingAccount = decode(any)?;
+
+                (account.base_vesting_account, VestingSchedule::Delayed)
+            }
+            PERIODIC_VESTING_ACCOUNT_TYPE_URL => {
+                let account: vesting::PeriodicVestingAccount = decode(any)?;
+                let periods = account
+                    .vesting_periods
+                    .iter()
+                    .map(|p| {
+                        Ok(VestingPeriod {
+                            length: p.length,
+                            amount: coins(&p.amount)?,
+                        })
+                    })
+                    .collect::<Result<Vec<VestingPeriod>, AccountError>>()?;
+
+                (
+                    account.base_vesting_account,
+                    VestingSchedule::Periodic {
+                        start_time: account.start_time,
+                        periods,
+                    },
+                )
+            }
+            PERMANENT_LOCKED_ACCOUNT_TYPE_URL => {
+                let account: vesting::PermanentLockedAccount = decode(any)?;
+
+                (
+                    account.base_vesting_account,
+                    VestingSchedule::PermanentLocked,
+                )
+            }
+            type_url => {
+                return Err(AccountError::Empty(format!(
+                    "{} is not a vesting account",
+                    type_url
+                )))
+            }
+        };
+        let base_vesting_account = base_vesting_account
+            .ok_or_else(|| AccountError::Empty("missing base vesting account".to_string()))?;
+        let base_account = base_vesting_account
+            .base_account
+            .ok_or_else(|| AccountError::Empty("missing base account".to_string()))?;
+
+        Ok(VestingAccount {
+            base_account: base_account.try_into()?,
+            original_vesting: coins(&base_vesting_account.original_vesting)?,
+            delegated_free: coins(&base_vesting_account.delegated_free)?,
+            delegated_vesting: coins(&base_vesting_account.delegated_vesting)?,
+            end_time: base_vesting_account.end_time,
+            schedule,
+        })
+    }
+}
+
+fn decode<M: Message + Default>(any: &Any) -> Result<M, AccountError> {
+    M::decode(&any.value[..]).map_err(|e| AccountError::Decode(e.into()))
+}
+
+fn coins(coins: &[crate::cosmos_modules::base::Coin]) -> Result<Vec<Coin>, AccountError> {
+    coins
+        .iter()
+        .map(|c| Coin::try_from(c).map_err(|e| AccountError::Decode(e.into())))
+        .collect()
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
+}
+
+/// The vested fraction `elapsed / duration` scaled by 10^18, computed like the SDK's
+/// `sdk.NewDec(elapsed).Quo(sdk.NewDec(duration))`: truncated to 36 decimals and then rounded half to even to 18.
+/// Requires `elapsed <= duration`.
+fn vested_ratio(elapsed: u128, duration: u128) -> u128 {
+    let scaled = elapsed * DEC_SCALE;
+    let (ratio, remainder) = (scaled / duration, scaled % duration);
+    let dropped = remainder * DEC_SCALE / duration;
+
+    if dropped * 2 > DEC_SCALE || (dropped * 2 == DEC_SCALE && ratio % 2 == 1) {
+        ratio + 1
+    } else {
+        ratio
+    }
+}
+
+/// `amount * numerator / denominator` rounded half to even, like the SDK's `Dec.RoundInt`. Requires
+/// `numerator <= denominator`.
+fn mul_ratio(amount: u128, numerator: u128, denominator: u128) -> u128 {
+    let (quotient, remainder) = (amount / denominator, amount % denominator);
+    let product = remainder * numerator;
+    let (rounded, leftover) = (product / denominator, product % denominator);
+    let result = quotient * numerator + rounded;
+
+    if leftover * 2 > denominator || (leftover * 2 == denominator && result % 2 == 1) {
+        result + 1
+    } else {
+        result
+    }
+}
+
+fn add_coins(a: &[Coin], b: &[Coin]) -> Vec<Coin> {
+    let mut sum = a.to_vec();
+
+    for coin in b {
+        match sum.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount = c.amount.saturating_add(coin.amount),
+            None => sum.push(coin.clone()),
+        }
+    }
+
+    sum
+}
+
+/// Subtracts `b` from `a` per denom, dropping denoms that reach zero
+fn sub_coins(a: &[Coin], b: &[Coin]) -> Vec<Coin> {
+    a.iter()
+        .map(|coin| {
+            let subtracted = b
+                .iter()
+                .filter(|c| c.denom == coin.denom)
+                .map(|c| c.amount)
+                .sum::<u128>();
+
+            Coin::new(coin.amount.saturating_sub(subtracted), &coin.denom)
+        })
+        .filter(|c| c.amount > 0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn account(schedule: VestingSchedule) -> VestingAccount {
+        VestingAccount {
+            base_account: BaseAccount {
+                address: "cosmos1vesting".to_string(),
+                pub_key: None,
+                account_number: 1,
+                sequence: 0,
+            },
+            original_vesting: vec![Coin::new(1000, "uatom")],
+            delegated_free: Vec::new(),
+            delegated_vesting: vec![Coin::new(100, "uatom")],
+            end_time: 2000,
+            schedule,
+        }
+    }
+
+    #[test]
+    fn computes_continuous_vesting() {
+        let account = account(VestingSchedule::Continuous { start_time: 1000 });
+
+        assert!(account.vested_coins(at(1000)).is_empty());
+        assert_eq!(
+            account.vested_coins(at(1250)),
+            vec![Coin::new(250, "uatom")]
+        );
+        assert_eq!(
+            account.vesting_coins(at(1250)),
+            vec![Coin::new(750, "uatom")]
+        );
+        assert_eq!(
+            account.locked_coins(at(1250)),
+            vec![Coin::new(650, "uatom")]
+        );
+        assert_eq!(
+            account.vested_coins(at(3000)),
+            vec![Coin::new(1000, "uatom")]
+        );
+        assert!(account.locked_coins(at(1950)).is_empty());
+    }
+
+    #[test]
+    fn rounds_continuous_vesting_like_the_sdk() {
+        // 1 million EVMOS vesting over 3 seconds. The SDK rounds a third to 18 decimals before multiplying.
+        let account = VestingAccount {
+            original_vesting: vec![Coin::new(10u128.pow(24), "aevmos")],
+            delegated_vesting: Vec::new(),
+            ..account(VestingSchedule::Continuous { start_time: 1997 })
+        };
+
+        assert_eq!(
+            account.vested_coins(at(1998)),
+            vec![Coin::new(333333333333333333000000, "aevmos")]
+        );
+        assert_eq!(vested_ratio(1, 2), DEC_SCALE / 2);
+        assert_eq!(vested_ratio(2, 3), 666666666666666667);
+        assert_eq!(
+            mul_ratio(10u128.pow(27) + 7, vested_ratio(2, 7), DEC_SCALE),
+            285714285714285714000000002
+        );
+    }
+
+    #[test]
+    fn computes_delayed_and_periodic_vesting() {
+        let delayed = account(VestingSchedule::Delayed);
+
+        assert!(delayed.vested_coins(at(1999)).is_empty());
+        assert_eq!(
+            delayed.vested_coins(at(2000)),
+            vec![Coin::new(1000, "uatom")]
+        );
+
+        let periodic = account(VestingSchedule::Periodic {
+            start_time: 1000,
+            periods: vec![
+                VestingPeriod {
+                    length: 500,
+                    amount: vec![Coin::new(400, "uatom")],
+                },
+                VestingPeriod {
+                    length: 500,
+                    amount: vec![Coin::new(600, "uatom")],
+                },
+            ],
+        });
+
+        assert!(periodic.vested_coins(at(1499)).is_empty());
+        assert_eq!(
+            periodic.vested_coins(at(1500)),
+            vec![Coin::new(400, "uatom")]
+        );
+        assert_eq!(
+            periodic.vesting_coins(at(1700)),
+            vec![Coin::new(600, "uatom")]
+        );
+        assert_eq!(
+            periodic.vested_coins(at(2000)),
+            vec![Coin::new(1000, "uatom")]
+        );
+    }
+
+    #[test]
+    fn decodes_vesting_accounts() {
+        let any = Any {
+            type_url: DELAYED_VESTING_ACCOUNT_TYPE_URL.to_string(),
+            value: vesting::DelayedVestingAccount {
+                base_vesting_account: Some(vesting::BaseVestingAccount {
+                    base_account: Some(crate::cosmos_modules::auth::BaseAccount {
+                        address: "cosmos1vesting".to_string(),
+                        pub_key: None,
+                        account_number: 7,
+                        sequence: 3,
+                    }),
+                    original_vesting: vec![Coin::new(1000, "uatom").into()],
+                    delegated_free: vec![],
+                    delegated_vesting: vec![],
+                    end_time: 2000,
+                }),
+            }
+            .encode_to_vec(),
+        };
+        let account = VestingAccount::try_from(&any).unwrap();
+
+        assert_eq!(account.base_account.sequence, 3);
+        assert_eq!(account.schedule, VestingSchedule::Delayed);
+        assert_eq!(account.original_vesting, vec![Coin::new(1000, "uatom")]);
+        assert!(VestingAccount::try_from(&Any {
+            type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
+            value: vec![],
+        })
+        .is_err());
+    }
+}
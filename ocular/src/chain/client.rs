@@ -1,7 +1,14 @@
 #![warn(unused_qualifications)]
 
 use crate::{
-    chain::{client::cache::Cache, config::ChainClientConfig},
+    account::{AccountDecoder, AccountDecoders},
+    chain::{
+        client::{
+            assets::AssetListCache, cache::Cache, height::HeightCache, sequence::SequenceManager,
+            tx_log::TxLog,
+        },
+        config::ChainClientConfig,
+    },
     error::{ChainClientError, ChainRegistryError, RpcError},
     keyring::Keyring,
     registry::get_chain,
@@ -12,10 +19,14 @@ use tendermint_rpc::{self, WebSocketClient, WebSocketClientDriver};
 use super::ChainName;
 
 pub mod airdrop;
+pub mod assets;
 pub mod cache;
 pub mod grpc;
+pub mod height;
 pub mod query;
+pub mod sequence;
 pub mod tx;
+pub mod tx_log;
 
 type RpcHttpClient = tendermint_rpc::HttpClient;
 
@@ -25,6 +36,15 @@ pub struct ChainClient {
     pub rpc_client: RpcHttpClient,
     pub cache: Option<Cache>,
     pub connection_retry_attempts: u8,
+    pub sequence_manager: SequenceManager,
+    /// Every broadcast tx is recorded here. Set to `None` to disable logging.
+    pub tx_log: Option<TxLog>,
+    /// Latest block height used to compute tx timeouts
+    pub height_cache: HeightCache,
+    /// Decoders for chain specific account types returned by [`ChainClient::query_account`]
+    pub account_decoders: AccountDecoders,
+    /// The chain registry asset list used to convert amounts, fetched when first needed
+    pub asset_list_cache: AssetListCache,
 }
 
 impl ChainClient {
@@ -46,6 +66,11 @@ impl ChainClient {
             rpc_client,
             cache,
             connection_retry_attempts,
+            sequence_manager: SequenceManager::default(),
+            tx_log: TxLog::try_default(),
+            height_cache: HeightCache::default(),
+            account_decoders: AccountDecoders::default(),
+            asset_list_cache: AssetListCache::default(),
         })
     }
 }
@@ -57,6 +82,8 @@ pub struct ChainClientBuilder {
     keyring: Option<Keyring>,
     cache: Option<Cache>,
     connection_retry_attempts: Option<u8>,
+    tx_log: Option<TxLog>,
+    account_decoders: AccountDecoders,
 }
 
 impl ChainClientBuilder {
@@ -68,6 +95,8 @@ impl ChainClientBuilder {
             keyring: None,
             cache: None,
             connection_retry_attempts: None,
+            tx_log: TxLog::try_default(),
+            account_decoders: AccountDecoders::default(),
         }
     }
 
@@ -104,6 +133,11 @@ impl ChainClientBuilder {
             rpc_client,
             cache: Some(cache),
             connection_retry_attempts,
+            sequence_manager: SequenceManager::default(),
+            tx_log: self.tx_log,
+            height_cache: HeightCache::default(),
+            account_decoders: self.account_decoders,
+            asset_list_cache: AssetListCache::default(),
         })
     }
 
@@ -126,6 +160,23 @@ impl ChainClientBuilder {
         self.cache = Some(cache);
         self
     }
+
+    /// Sets where broadcast txs are logged. Pass `None` to disable logging. Defaults to ~/.ocular/logs/txs.
+    pub fn with_tx_log(mut self, tx_log: Option<TxLog>) -> ChainClientBuilder {
+        self.tx_log = tx_log;
+        self
+    }
+
+    /// Registers a decoder for accounts of a chain specific type, which [`ChainClient::query_account`] then returns as
+    /// [`crate::account::Account::Custom`]
+    pub fn with_account_decoder(
+        mut self,
+        type_url: &str,
+        decoder: AccountDecoder,
+    ) -> ChainClientBuilder {
+        self.account_decoders.register(type_url, decoder);
+        self
+    }
 }
 
 fn get_client(chain_name: &str) -> Result<ChainClient, ChainClientError> {
@@ -152,6 +203,11 @@ fn get_client(chain_name: &str) -> Result<ChainClient, ChainClientError> {
         rpc_client,
         cache: Some(cache),
         connection_retry_attempts: 5,
+        sequence_manager: SequenceManager::default(),
+        tx_log: TxLog::try_default(),
+        height_cache: HeightCache::default(),
+        account_decoders: AccountDecoders::default(),
+        asset_list_cache: AssetListCache::default(),
     })
 }
 
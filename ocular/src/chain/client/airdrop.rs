@@ -6,7 +6,7 @@ use crate::{
     account::AccountInfo,
     chain::config::ChainClientConfig,
     cosmos_modules,
-    error::{AirdropError, ChainClientError},
+    error::{AirdropError, ChainClientError, TxError},
     keyring::Keyring,
     tx::{Any, Coin, MultiSendIo, Payment, PaymentsToml, TxMetadata},
 };
@@ -21,14 +21,16 @@ use cosmrs::{
 
 use prost::Message;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, time::SystemTime};
+use std::{collections::HashMap, time::Duration};
 use std::{fs, path::Path, str::FromStr};
 use uuid::Uuid;
 
-use super::{tx::BroadcastCommitResponse, ChainClient};
+use super::{
+    tx::{Authorization, TxResponse},
+    ChainClient,
+};
 
 const MSG_MULTI_SEND_URL: &str = "/cosmos.bank.v1beta1.MsgMultiSend";
-const GENERIC_AUTHORIZATION_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
 
 impl ChainClient {
     pub async fn verify_multi_send_grant(
@@ -45,33 +47,10 @@ impl ChainClient {
         // 1. The grant either has no expiration, or an expiration with more than 60 seconds remaining.
         // 2. The grant contains a generic authorization
         let grant_found = grants.iter().any(|g| {
-            if g.expiration.is_none() {
-                return false;
-            }
-
-            let expiration = g.expiration.clone().unwrap();
-            let cutoff = i64::try_from(
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs()
-                    + 60,
-            )
-            .expect("failed to derive time from system clock");
-            if expiration.seconds <= cutoff {
-                return false;
-            }
-            if g.authorization.is_none() {
-                return false;
-            }
-            // I don't actually think this is a necessary check as there is no way to specify
+            // I don't actually think the authorization check is necessary as there is no way to specify
             // authorization for MultiSend without using a generic one
-            let authorization = g.authorization.clone().unwrap();
-            if authorization.type_url.as_str() != GENERIC_AUTHORIZATION_URL {
-                return false;
-            }
-
-            true
+            !g.expires_within(Duration::from_secs(60))
+                && matches!(g.authorization, Authorization::Generic { .. })
         });
         if !grant_found {
             return Err(ChainClientError::UnauthorizedTx(format!(
@@ -90,22 +69,12 @@ impl ChainClient {
         grantee: &AccountInfo,
         payments: Vec<Payment>,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
         self.verify_multi_send_grant(granter, &grantee.id(&self.config.account_prefix)?)
             .await?;
 
-        let (inputs, outputs) = multi_send_args_from_payments(&granter.to_string(), payments);
-        let msgs: Vec<Any> = vec![MsgMultiSend {
-            inputs: inputs
-                .iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-            outputs: outputs
-                .iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        }
-        .to_any()?];
+        let (inputs, outputs) = multi_send_args_from_payments(&granter.to_string(), payments)?;
+        let msgs: Vec<Any> = vec![self.multi_send_msg(inputs, outputs)?];
 
         self.execute_authorized_tx(grantee, msgs, tx_metadata).await
     }
@@ -114,7 +83,7 @@ impl ChainClient {
         &mut self,
         path: &str,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
         let payments_toml = read_payments_toml(path)?;
         let grantee = match payments_toml.grantee_key_name {
             Some(g) => self.keyring.get_account(&g),
@@ -128,8 +97,10 @@ impl ChainClient {
         let granter = self.keyring.get_account(&payments_toml.sender_key_name)?;
 
         // add fee_payer and fee_granter values to metadata if present
-        let basic_tx_metadata = self.get_basic_tx_metadata().await?;
-        let tx_metadata = tx_metadata.unwrap_or(basic_tx_metadata);
+        let tx_metadata = match tx_metadata {
+            Some(tm) => tm,
+            None => self.get_basic_tx_metadata().await?,
+        };
 
         self.execute_delegated_airdrop(
             &granter.id(&self.config.account_prefix)?,
@@ -145,9 +116,9 @@ impl ChainClient {
         sender: &AccountInfo,
         payments: Vec<Payment>,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
         let (inputs, outputs) =
-            multi_send_args_from_payments(&sender.address(&self.config.account_prefix)?, payments);
+            multi_send_args_from_payments(&sender.address(&self.config.account_prefix)?, payments)?;
         self.multi_send(sender, inputs, outputs, tx_metadata).await
     }
 
@@ -155,7 +126,7 @@ impl ChainClient {
         &mut self,
         path: &str,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
         let payments_toml = read_payments_toml(path)?;
         let sender = self.keyring.get_account(&payments_toml.sender_key_name)?;
         // TO-DO user the metadata from the toml
@@ -166,21 +137,19 @@ impl ChainClient {
 
 /// Creates arguments for a MultiSend transaction from a vec of [`Payment`]. We require a single
 /// `Input` because, for Authz transactions, the tx will be considered to have multiple signers if
-/// there are multiple `Input`s, which is not allowed by the authz msg `MsgExec`.
+/// there are multiple `Input`s, which is not allowed by the authz msg `MsgExec`. Errors if the total of a denom
+/// overflows.
 pub fn multi_send_args_from_payments(
     sender_addr: &str,
     payments: Vec<Payment>,
-) -> (Vec<MultiSendIo>, Vec<MultiSendIo>) {
+) -> Result<(Vec<MultiSendIo>, Vec<MultiSendIo>), TxError> {
     let mut outputs = Vec::<MultiSendIo>::new();
-    let mut coins_total = HashMap::<String, u64>::new();
-    payments.iter().for_each(|p| {
-        let key = p.denom.clone();
-        let value = p.amount;
-        if coins_total.contains_key(&key) {
-            coins_total.insert(key.clone(), coins_total.get(&key).unwrap() + value);
-        } else {
-            coins_total.insert(key, value);
-        }
+    let mut coins_total = HashMap::<String, u128>::new();
+    for p in payments.iter() {
+        let total = coins_total.entry(p.denom.clone()).or_insert(0);
+        *total = total.checked_add(p.amount).ok_or_else(|| {
+            TxError::TypeConversion(format!("total payments of {} overflow", p.denom))
+        })?;
 
         outputs.push(MultiSendIo {
             address: p.recipient.clone(),
@@ -189,7 +158,7 @@ pub fn multi_send_args_from_payments(
                 amount: p.amount,
             }],
         });
-    });
+    }
 
     let coins_input = coins_total
         .iter()
@@ -202,7 +171,7 @@ pub fn multi_send_args_from_payments(
         address: sender_addr.to_string(),
         coins: coins_input,
     }];
-    (input, outputs)
+    Ok((input, outputs))
 }
 
 // TO-DO different error type.
@@ -313,13 +282,13 @@ mod tests {
         let num_outputs = rng.gen_range(1..25);
         let accounts = generate_accounts(num_outputs);
         let payments = generate_payments_single_denom(prefix, "utest", &accounts);
-        let args = multi_send_args_from_payments(&sender_address.clone(), payments);
-        let input_total: u64 = args
+        let args = multi_send_args_from_payments(&sender_address.clone(), payments).unwrap();
+        let input_total: u128 = args
             .0
             .iter()
             .map(|io: &MultiSendIo| io.coins[0].amount)
             .sum();
-        let output_total: u64 = args
+        let output_total: u128 = args
             .1
             .iter()
             .map(|io: &MultiSendIo| io.coins[0].amount)
@@ -345,6 +314,22 @@ mod tests {
         assert_eq!(input_total, output_total);
     }
 
+    #[test]
+    fn rejects_overflowing_payment_totals() {
+        let payment = Payment {
+            recipient: AccountInfo::new("").address("test").unwrap(),
+            amount: u128::MAX / 2 + 1,
+            denom: "utest".to_string(),
+        };
+        let sender_address = AccountInfo::new("").address("test").unwrap();
+
+        assert!(multi_send_args_from_payments(&sender_address, vec![payment.clone()]).is_ok());
+        assert!(matches!(
+            multi_send_args_from_payments(&sender_address, vec![payment.clone(), payment]),
+            Err(TxError::TypeConversion(_))
+        ));
+    }
+
     fn generate_accounts(n: u64) -> Vec<AccountInfo> {
         let mut accounts = Vec::<AccountInfo>::new();
 
@@ -0,0 +1,115 @@
+//! Conversions between human readable amounts like `"1.5atom"` and base denom [`Coin`]s. Denom units are looked up
+//! in the chain's [`AssetList`] from the chain registry, falling back to the bank module's denom metadata.
+use std::sync::Mutex;
+
+use crate::{
+    error::ChainClientError,
+    registry::{
+        assets::{split_amount, Asset, AssetList},
+        get_assets,
+    },
+    tx::Coin,
+};
+
+use super::ChainClient;
+
+/// Holds the chain's asset list once it has been fetched from the chain registry, so amount conversions don't
+/// refetch it. Uses interior mutability so it can be updated from methods that only borrow the client.
+#[derive(Debug, Default)]
+pub struct AssetListCache {
+    asset_list: Mutex<Option<AssetList>>,
+}
+
+impl AssetListCache {
+    pub fn get(&self) -> Option<AssetList> {
+        self.asset_list.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, asset_list: AssetList) {
+        *self.asset_list.lock().unwrap() = Some(asset_list);
+    }
+
+    /// The asset list is fetched again the next time it's needed
+    pub fn clear(&self) {
+        *self.asset_list.lock().unwrap() = None;
+    }
+}
+
+impl ChainClient {
+    /// Gets the chain's asset list from the chain registry, or an empty list if there is none. The list is fetched
+    /// once and then kept in the client's `asset_list_cache`.
+    pub async fn get_asset_list(&self) -> Result<AssetList, ChainClientError> {
+        if let Some(asset_list) = self.asset_list_cache.get() {
+            return Ok(asset_list);
+        }
+
+        let asset_list = get_assets(&self.config.chain_name)
+            .await?
+            .unwrap_or_default();
+        self.asset_list_cache.set(asset_list.clone());
+
+        Ok(asset_list)
+    }
+
+    /// The chain's registry asset list, or an empty list if the registry can't be reached so that lookups fall back
+    /// to the bank module's denom metadata
+    async fn registry_asset_list(&self) -> AssetList {
+        self.get_asset_list().await.unwrap_or_default()
+    }
+
+    /// Finds the asset with a denom unit, alias or symbol matching `denom`. Searches the chain registry first and
+    /// then the bank module's denom metadata.
+    pub async fn get_asset(&mut self, denom: &str) -> Result<Asset, ChainClientError> {
+        if let Some(asset) = self.registry_asset_list().await.find(denom) {
+            return Ok(asset.clone());
+        }
+
+        self.query_all_denoms_metadata()
+            .await?
+            .into_iter()
+            .map(Asset::from)
+            .find(|a| a.unit(denom).is_some())
+            .ok_or_else(|| ChainClientError::ModuleQuery(format!("no asset with denom {}", denom)))
+    }
+
+    /// Parses a human readable amount like `"1.5atom"` or `"250 OSMO"` into a coin of the asset's base denom
+    pub async fn parse_amount(&mut self, amount: &str) -> Result<Coin, ChainClientError> {
+        let (_, denom) = split_amount(amount)?;
+
+        Ok(self.get_asset(denom).await?.parse_amount(amount)?)
+    }
+
+    /// Formats a base denom coin in display units, e.g. `1500000uatom` as `"1.5atom"`
+    pub async fn format_amount(&mut self, coin: &Coin) -> Result<String, ChainClientError> {
+        if let Some(asset) = self.registry_asset_list().await.find_by_base(&coin.denom) {
+            return Ok(asset.format_amount(coin.amount)?);
+        }
+
+        let metadata = self.query_denom_metadata(&coin.denom).await?;
+
+        Ok(Asset::from(metadata).format_amount(coin.amount)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caches_asset_list() {
+        let cache = AssetListCache::default();
+
+        assert!(cache.get().is_none());
+
+        cache.set(AssetList {
+            chain_name: "cosmoshub".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(cache.get().unwrap().chain_name, "cosmoshub");
+
+        cache.clear();
+
+        assert!(cache.get().is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Short lived caching of the latest block height, so that a batch of txs doesn't query it for every tx.
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How long a queried height is reused by default. Long enough to cover a batch of txs, short enough that a
+/// block delta timeout is still roughly relative to the current height.
+pub const DEFAULT_HEIGHT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Caches the latest block height for a fixed duration. Uses interior mutability so it can be updated from
+/// methods that only borrow the client.
+#[derive(Debug)]
+pub struct HeightCache {
+    ttl: Duration,
+    latest: Mutex<Option<(u64, Instant)>>,
+}
+
+impl Default for HeightCache {
+    fn default() -> HeightCache {
+        HeightCache::new(DEFAULT_HEIGHT_CACHE_TTL)
+    }
+}
+
+impl HeightCache {
+    /// A `ttl` of zero disables caching
+    pub fn new(ttl: Duration) -> HeightCache {
+        HeightCache {
+            ttl,
+            latest: Mutex::new(None),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached height if it hasn't expired
+    pub fn get(&self) -> Option<u64> {
+        let latest = self.latest.lock().unwrap();
+
+        latest
+            .filter(|(_, cached_at)| cached_at.elapsed() < self.ttl)
+            .map(|(height, _)| height)
+    }
+
+    pub fn set(&self, height: u64) {
+        *self.latest.lock().unwrap() = Some((height, Instant::now()));
+    }
+
+    pub fn clear(&self) {
+        *self.latest.lock().unwrap() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expires_cached_height() {
+        let cache = HeightCache::new(Duration::from_millis(50));
+
+        assert_eq!(cache.get(), None);
+
+        cache.set(100);
+
+        assert_eq!(cache.get(), Some(100));
+
+        std::thread::sleep(Duration::from_millis(60));
+
+        assert_eq!(cache.get(), None);
+
+        let disabled = HeightCache::new(Duration::ZERO);
+        disabled.set(100);
+
+        assert_eq!(disabled.get(), None);
+    }
+}
@@ -36,9 +36,11 @@ use crate::error::{ChainClientError, GrpcError, RpcError, TxError};
 
 use super::ChainClient;
 
+#[cfg(feature = "cosmwasm")]
+pub use self::wasm::*;
 pub use self::{
-    auth::*, authz::*, bank::*, distribution::*, evidence::*, gov::*, mint::*, params::*,
-    slashing::*, staking::*,
+    auth::*, authz::*, bank::*, distribution::*, evidence::*, feegrant::*, gov::*, mint::*,
+    params::*, slashing::*, staking::*, tx::*,
 };
 
 pub mod auth;
@@ -46,11 +48,15 @@ pub mod authz;
 pub mod bank;
 pub mod distribution;
 pub mod evidence;
+pub mod feegrant;
 pub mod gov;
 pub mod mint;
 pub mod params;
 pub mod slashing;
 pub mod staking;
+pub mod tx;
+#[cfg(feature = "cosmwasm")]
+pub mod wasm;
 
 pub type PageRequest = cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
 
@@ -1,13 +1,13 @@
 //! Query methods for the [Auth module](https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/auth/v1beta1/query.proto). If you need a query that does not have a method wrapper here, you can use the [`AuthQueryClient`] directly.
 use async_trait::async_trait;
 use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
-use prost::Message;
 use tonic::transport::Channel;
 
 use crate::{
-    account::BaseAccount,
+    account::{Account, VestingAccount},
     cosmos_modules::auth,
-    error::{ChainClientError, GrpcError},
+    error::{AccountError, ChainClientError, GrpcError},
+    tx::Any,
 };
 
 use super::{ChainClient, QueryClient};
@@ -25,46 +25,55 @@ impl QueryClient for AuthQueryClient {
 }
 
 impl ChainClient {
-    /// Gets the account on chain with the specified address
-    pub async fn query_account(&mut self, address: &str) -> Result<BaseAccount, ChainClientError> {
-        let mut query_client = self.get_query_client::<AuthQueryClient>().await?;
-        let request = auth::QueryAccountRequest {
-            address: address.to_string(),
-        };
-        let response = query_client
-            .account(request)
-            .await
-            .map_err(GrpcError::Request)?
-            .into_inner();
-        let any = response.account.unwrap();
+    /// Gets the account on chain with the specified address. Accounts of types ocular doesn't support are decoded
+    /// with the client's `account_decoders`, or by finding the `BaseAccount` in its most common position.
+    pub async fn query_account(&mut self, address: &str) -> Result<Account, ChainClientError> {
+        let any = self.query_account_any(address).await?;
+
+        Ok(self.account_decoders.decode(&any)?)
+    }
+
+    /// Gets the vesting account on chain with the specified address. Errors if it isn't a vesting account.
+    pub async fn query_vesting_account(
+        &mut self,
+        address: &str,
+    ) -> Result<VestingAccount, ChainClientError> {
+        let any = self.query_account_any(address).await?;
 
-        Ok(auth::BaseAccount::decode(&any.value as &[u8])
-            .unwrap()
-            .try_into()?)
+        Ok(VestingAccount::try_from(&any)?)
     }
 
     /// Gets all accounts
     pub async fn query_accounts(
         &mut self,
         pagination: Option<PageRequest>,
-    ) -> Result<Vec<BaseAccount>, ChainClientError> {
+    ) -> Result<Vec<Account>, ChainClientError> {
         let mut query_client = self.get_query_client::<AuthQueryClient>().await?;
         let request = auth::QueryAccountsRequest { pagination };
-        let base_accounts = query_client
+
+        Ok(query_client
             .accounts(request)
             .await
             .map_err(GrpcError::Request)?
             .into_inner()
             .accounts
             .iter()
-            .map(|any| auth::BaseAccount::decode(&any.value as &[u8]).unwrap())
-            .collect::<Vec<auth::BaseAccount>>();
-        let mut accounts = Vec::<BaseAccount>::new();
+            .map(|any| self.account_decoders.decode(any))
+            .collect::<Result<Vec<Account>, AccountError>>()?)
+    }
 
-        for ba in base_accounts {
-            accounts.push(ba.try_into()?)
-        }
+    async fn query_account_any(&mut self, address: &str) -> Result<Any, ChainClientError> {
+        let mut query_client = self.get_query_client::<AuthQueryClient>().await?;
+        let request = auth::QueryAccountRequest {
+            address: address.to_string(),
+        };
 
-        Ok(accounts)
+        query_client
+            .account(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .account
+            .ok_or_else(|| AccountError::Empty(format!("no account data for {}", address)).into())
     }
 }
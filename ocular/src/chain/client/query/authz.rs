@@ -1,17 +1,88 @@
 //! Query methods for the [Authz module](https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/authz/v1beta1/query.proto). If you need a query that does not have a method wrapper here, you can use the [`AuthzQueryClient`] directly.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use crate::{
+    chain::client::tx::Authorization,
     cosmos_modules::authz::{self, *},
-    error::{ChainClientError, GrpcError},
+    error::{ChainClientError, GrpcError, TxError},
+    tx::Any,
+    Timestamp,
 };
 use async_trait::async_trait;
-use tonic::transport::Channel;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse;
+use prost::Message;
+use tonic::{client::Grpc, codec::ProstCodec, transport::Channel};
 
-use super::{ChainClient, QueryClient};
+use super::{ChainClient, PageRequest, QueryClient};
 
 /// The authz module's query client proto definition
 pub type AuthzQueryClient = authz::query_client::QueryClient<Channel>;
 pub type Grant = authz::Grant;
 
+/// Number of grants requested per page when paging through all results
+const GRANTS_PAGE_LIMIT: u64 = 100;
+
+/// An authz grant with its authorization decoded
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthzGrant {
+    pub granter: String,
+    pub grantee: String,
+    pub authorization: Authorization,
+    pub expiration: Option<Timestamp>,
+}
+
+impl AuthzGrant {
+    /// Decodes a grant's authorization
+    pub fn new(granter: &str, grantee: &str, grant: Grant) -> Result<AuthzGrant, TxError> {
+        let authorization = grant
+            .authorization
+            .ok_or_else(|| TxError::TypeConversion("grant has no authorization".to_string()))?;
+
+        Ok(AuthzGrant {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            authorization: Authorization::try_from(authorization)?,
+            expiration: grant.expiration,
+        })
+    }
+
+    /// Returns true if the grant has expired. Grants without an expiration never expire.
+    pub fn is_expired(&self) -> bool {
+        self.expires_within(Duration::ZERO)
+    }
+
+    /// Returns true if the grant expires within `duration` from now, or already has. Every expiration is before a
+    /// time too far in the future to represent, so for durations that overflow any grant with an expiration does.
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        let cutoff = match SystemTime::now()
+            .checked_add(duration)
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        {
+            Some(cutoff) => cutoff,
+            None => return self.expiration.is_some(),
+        };
+
+        self.expiration.as_ref().map_or(false, |e| {
+            (e.seconds, e.nanos) <= (cutoff.as_secs() as i64, cutoff.subsec_nanos() as i32)
+        })
+    }
+}
+
+impl TryFrom<GrantAuthorization> for AuthzGrant {
+    type Error = TxError;
+
+    fn try_from(grant: GrantAuthorization) -> Result<AuthzGrant, Self::Error> {
+        AuthzGrant::new(
+            &grant.granter,
+            &grant.grantee,
+            Grant {
+                authorization: grant.authorization,
+                expiration: grant.expiration,
+            },
+        )
+    }
+}
+
 #[async_trait]
 impl QueryClient for AuthzQueryClient {
     type Transport = Channel;
@@ -21,6 +92,107 @@ impl QueryClient for AuthzQueryClient {
     }
 }
 
+// The GranterGrants and GranteeGrants queries were added in Cosmos SDK v0.46, after the SDK version the
+// cosmos-sdk-proto definitions are generated from, so their messages and client are defined here.
+
+/// `cosmos.authz.v1beta1.GrantAuthorization`: a grant along with its granter and grantee
+#[derive(Clone, PartialEq, Message)]
+pub struct GrantAuthorization {
+    #[prost(string, tag = "1")]
+    pub granter: String,
+    #[prost(string, tag = "2")]
+    pub grantee: String,
+    #[prost(message, optional, tag = "3")]
+    pub authorization: Option<Any>,
+    #[prost(message, optional, tag = "4")]
+    pub expiration: Option<Timestamp>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryGranterGrantsRequest {
+    #[prost(string, tag = "1")]
+    pub granter: String,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryGranteeGrantsRequest {
+    #[prost(string, tag = "1")]
+    pub grantee: String,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageRequest>,
+}
+
+/// Response of both the GranterGrants and GranteeGrants queries
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryGrantAuthorizationsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub grants: Vec<GrantAuthorization>,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageResponse>,
+}
+
+/// Query client for the authz module's GranterGrants and GranteeGrants queries
+#[derive(Clone, Debug)]
+pub struct AuthzGrantsQueryClient {
+    inner: Grpc<Channel>,
+}
+
+#[async_trait]
+impl QueryClient for AuthzGrantsQueryClient {
+    type Transport = Channel;
+
+    async fn connect(endpoint: String) -> Result<Self, tonic::transport::Error> {
+        let channel = tonic::transport::Endpoint::new(endpoint)?.connect().await?;
+
+        Ok(AuthzGrantsQueryClient {
+            inner: Grpc::new(channel),
+        })
+    }
+}
+
+impl AuthzGrantsQueryClient {
+    pub async fn granter_grants(
+        &mut self,
+        request: QueryGranterGrantsRequest,
+    ) -> Result<QueryGrantAuthorizationsResponse, tonic::Status> {
+        self.unary("/cosmos.authz.v1beta1.Query/GranterGrants", request)
+            .await
+    }
+
+    pub async fn grantee_grants(
+        &mut self,
+        request: QueryGranteeGrantsRequest,
+    ) -> Result<QueryGrantAuthorizationsResponse, tonic::Status> {
+        self.unary("/cosmos.authz.v1beta1.Query/GranteeGrants", request)
+            .await
+    }
+
+    async fn unary<T: Message + Send + Sync + 'static>(
+        &mut self,
+        path: &'static str,
+        request: T,
+    ) -> Result<QueryGrantAuthorizationsResponse, tonic::Status> {
+        self.inner.ready().await.map_err(|e| {
+            tonic::Status::new(
+                tonic::Code::Unknown,
+                format!("Service was not ready: {}", e),
+            )
+        })?;
+
+        Ok(self
+            .inner
+            .unary(
+                tonic::Request::new(request),
+                http::uri::PathAndQuery::from_static(path),
+                ProstCodec::default(),
+            )
+            .await?
+            .into_inner())
+    }
+}
+
 impl ChainClient {
     /// Gets all grants between `granter` and `grantee` for the given msg type
     pub async fn query_authz_grant(
@@ -28,21 +200,173 @@ impl ChainClient {
         granter: &str,
         grantee: &str,
         msg_type_url: &str,
-    ) -> Result<Vec<Grant>, ChainClientError> {
+    ) -> Result<Vec<AuthzGrant>, ChainClientError> {
         let mut query_client = self.get_query_client::<AuthzQueryClient>().await?;
-        let request = QueryGrantsRequest {
-            granter: granter.to_string(),
-            grantee: grantee.to_string(),
-            msg_type_url: msg_type_url.to_string(),
-            // TODO: Support pagination if use case arises
-            pagination: None,
+        let mut grants = Vec::new();
+        let mut next_key = Vec::new();
+
+        loop {
+            let request = QueryGrantsRequest {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                msg_type_url: msg_type_url.to_string(),
+                pagination: Some(page_request(next_key)),
+            };
+            let response = query_client
+                .grants(request)
+                .await
+                .map_err(GrpcError::Request)?
+                .into_inner();
+
+            for grant in response.grants {
+                grants.push(AuthzGrant::new(granter, grantee, grant)?);
+            }
+
+            next_key = match response.pagination {
+                Some(p) if !p.next_key.is_empty() => p.next_key,
+                _ => return Ok(grants),
+            };
+        }
+    }
+
+    /// Gets every grant given by `granter`, paging through all results. Requires a chain running Cosmos SDK v0.46
+    /// or later.
+    pub async fn query_granter_grants(
+        &mut self,
+        granter: &str,
+    ) -> Result<Vec<AuthzGrant>, ChainClientError> {
+        let mut query_client = self.get_query_client::<AuthzGrantsQueryClient>().await?;
+        let mut grants = Vec::new();
+        let mut next_key = Vec::new();
+
+        loop {
+            let request = QueryGranterGrantsRequest {
+                granter: granter.to_string(),
+                pagination: Some(page_request(next_key)),
+            };
+            let response = query_client
+                .granter_grants(request)
+                .await
+                .map_err(GrpcError::Request)?;
+
+            for grant in response.grants {
+                grants.push(AuthzGrant::try_from(grant)?);
+            }
+
+            next_key = match response.pagination {
+                Some(p) if !p.next_key.is_empty() => p.next_key,
+                _ => return Ok(grants),
+            };
+        }
+    }
+
+    /// Gets every grant held by `grantee`, paging through all results. Requires a chain running Cosmos SDK v0.46
+    /// or later.
+    pub async fn query_grantee_grants(
+        &mut self,
+        grantee: &str,
+    ) -> Result<Vec<AuthzGrant>, ChainClientError> {
+        let mut query_client = self.get_query_client::<AuthzGrantsQueryClient>().await?;
+        let mut grants = Vec::new();
+        let mut next_key = Vec::new();
+
+        loop {
+            let request = QueryGranteeGrantsRequest {
+                grantee: grantee.to_string(),
+                pagination: Some(page_request(next_key)),
+            };
+            let response = query_client
+                .grantee_grants(request)
+                .await
+                .map_err(GrpcError::Request)?;
+
+            for grant in response.grants {
+                grants.push(AuthzGrant::try_from(grant)?);
+            }
+
+            next_key = match response.pagination {
+                Some(p) if !p.next_key.is_empty() => p.next_key,
+                _ => return Ok(grants),
+            };
+        }
+    }
+}
+
+fn page_request(key: Vec<u8>) -> PageRequest {
+    PageRequest {
+        key,
+        offset: 0,
+        limit: GRANTS_PAGE_LIMIT,
+        count_total: false,
+        reverse: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(expiration: Option<Timestamp>) -> AuthzGrant {
+        AuthzGrant::try_from(GrantAuthorization {
+            granter: "cosmos1granter".to_string(),
+            grantee: "cosmos1grantee".to_string(),
+            authorization: Some(Authorization::generic("/cosmos.bank.v1beta1.MsgSend").to_any()),
+            expiration,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn checks_grant_expiration() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let expiring = grant(Some(Timestamp {
+            seconds: now + 30,
+            nanos: 0,
+        }));
+
+        assert_eq!(expiring.granter, "cosmos1granter");
+        assert_eq!(
+            expiring.authorization,
+            Authorization::generic("/cosmos.bank.v1beta1.MsgSend")
+        );
+        assert!(!expiring.is_expired());
+        assert!(expiring.expires_within(Duration::from_secs(60)));
+        assert!(grant(Some(Timestamp {
+            seconds: now - 1,
+            nanos: 0
+        }))
+        .is_expired());
+        assert!(!grant(None).expires_within(Duration::from_secs(u32::MAX as u64)));
+        assert!(expiring.expires_within(Duration::MAX));
+        assert!(!grant(None).expires_within(Duration::MAX));
+    }
+
+    #[test]
+    fn decodes_grant_authorizations_response() {
+        let response = QueryGrantAuthorizationsResponse {
+            grants: vec![GrantAuthorization {
+                granter: "cosmos1granter".to_string(),
+                grantee: "cosmos1grantee".to_string(),
+                authorization: Some(Authorization::send(Vec::new()).to_any()),
+                expiration: None,
+            }],
+            pagination: Some(PageResponse {
+                next_key: vec![1],
+                total: 0,
+            }),
         };
+        let decoded =
+            QueryGrantAuthorizationsResponse::decode(&response.encode_to_vec()[..]).unwrap();
 
-        Ok(query_client
-            .grants(request)
-            .await
-            .map_err(GrpcError::Request)?
-            .into_inner()
-            .grants)
+        assert_eq!(decoded, response);
+        assert_eq!(
+            AuthzGrant::try_from(decoded.grants[0].clone())
+                .unwrap()
+                .authorization,
+            Authorization::send(Vec::new())
+        );
     }
 }
@@ -13,6 +13,9 @@ use super::{ChainClient, PageRequest, QueryClient};
 /// The bank module's query client proto definition
 pub type BankQueryClient = bank::query_client::QueryClient<Channel>;
 
+/// Number of denom metadata entries requested per page when paging through all results
+const DENOMS_METADATA_PAGE_LIMIT: u64 = 100;
+
 #[async_trait]
 impl QueryClient for BankQueryClient {
     type Transport = Channel;
@@ -99,6 +102,39 @@ impl ChainClient {
         Ok(response.metadatas)
     }
 
+    /// Gets the metadata for all coin denominations defined in the bank module, paging through all results
+    pub async fn query_all_denoms_metadata(
+        &mut self,
+    ) -> Result<Vec<bank::Metadata>, ChainClientError> {
+        let mut query_client = self.get_query_client::<BankQueryClient>().await?;
+        let mut metadatas = Vec::new();
+        let mut next_key = Vec::new();
+
+        loop {
+            let request = bank::QueryDenomsMetadataRequest {
+                pagination: Some(PageRequest {
+                    key: next_key,
+                    offset: 0,
+                    limit: DENOMS_METADATA_PAGE_LIMIT,
+                    count_total: false,
+                    reverse: false,
+                }),
+            };
+            let response = query_client
+                .denoms_metadata(request)
+                .await
+                .map_err(GrpcError::Request)?
+                .into_inner();
+
+            metadatas.extend(response.metadatas);
+
+            next_key = match response.pagination {
+                Some(p) if !p.next_key.is_empty() => p.next_key,
+                _ => return Ok(metadatas),
+            };
+        }
+    }
+
     /// Gets the supply of the specified coin denomination
     pub async fn query_supply(&mut self, denom: &str) -> Result<Coin, ChainClientError> {
         let mut query_client = self.get_query_client::<BankQueryClient>().await?;
@@ -2,9 +2,13 @@
 use async_trait::async_trait;
 use tonic::transport::Channel;
 
-use crate::cosmos_modules::distribution;
+use crate::{
+    cosmos_modules::distribution,
+    error::{ChainClientError, GrpcError, TxError},
+    tx::DecCoin,
+};
 
-use super::QueryClient;
+use super::{ChainClient, QueryClient};
 
 /// The distribution module's query client proto definition
 pub type DistributionQueryClient = distribution::query_client::QueryClient<Channel>;
@@ -17,3 +21,67 @@ impl QueryClient for DistributionQueryClient {
         Self::connect(endpoint).await
     }
 }
+
+impl ChainClient {
+    /// Gets the operator addresses of all validators `delegator` has delegated to
+    pub async fn query_delegator_validators(
+        &mut self,
+        delegator: &str,
+    ) -> Result<Vec<String>, ChainClientError> {
+        let mut query_client = self.get_query_client::<DistributionQueryClient>().await?;
+        let request = distribution::QueryDelegatorValidatorsRequest {
+            delegator_address: delegator.to_string(),
+        };
+
+        Ok(query_client
+            .delegator_validators(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .validators)
+    }
+
+    /// Gets the total unclaimed rewards of `delegator` across all of its delegations
+    pub async fn query_delegation_total_rewards(
+        &mut self,
+        delegator: &str,
+    ) -> Result<Vec<DecCoin>, ChainClientError> {
+        let mut query_client = self.get_query_client::<DistributionQueryClient>().await?;
+        let request = distribution::QueryDelegationTotalRewardsRequest {
+            delegator_address: delegator.to_string(),
+        };
+
+        Ok(query_client
+            .delegation_total_rewards(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .total
+            .iter()
+            .map(DecCoin::try_from)
+            .collect::<Result<Vec<DecCoin>, TxError>>()?)
+    }
+
+    /// Gets the unclaimed commission of the validator with operator address `validator`
+    pub async fn query_validator_commission(
+        &mut self,
+        validator: &str,
+    ) -> Result<Vec<DecCoin>, ChainClientError> {
+        let mut query_client = self.get_query_client::<DistributionQueryClient>().await?;
+        let request = distribution::QueryValidatorCommissionRequest {
+            validator_address: validator.to_string(),
+        };
+
+        Ok(query_client
+            .validator_commission(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .commission
+            .map(|c| c.commission)
+            .unwrap_or_default()
+            .iter()
+            .map(DecCoin::try_from)
+            .collect::<Result<Vec<DecCoin>, TxError>>()?)
+    }
+}
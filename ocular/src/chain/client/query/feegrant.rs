@@ -0,0 +1,71 @@
+//! Query methods for the [Feegrant module](https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/feegrant/v1beta1/query.proto). If you need a query that does not have a method wrapper here, you can use the [`FeegrantQueryClient`] directly.
+use crate::{
+    cosmos_modules::feegrant,
+    error::{ChainClientError, GrpcError},
+};
+use async_trait::async_trait;
+use tonic::transport::Channel;
+
+use super::{ChainClient, PageRequest, QueryClient};
+
+/// The feegrant module's query client proto definition
+pub type FeegrantQueryClient = feegrant::query_client::QueryClient<Channel>;
+/// A fee allowance from a granter to a grantee. The allowance can be decoded with
+/// [`crate::chain::client::tx::Allowance`]'s `TryFrom<Any>`.
+pub type FeeGrant = feegrant::Grant;
+
+#[async_trait]
+impl QueryClient for FeegrantQueryClient {
+    type Transport = Channel;
+
+    async fn connect(endpoint: String) -> Result<Self, tonic::transport::Error> {
+        Self::connect(endpoint).await
+    }
+}
+
+impl ChainClient {
+    /// Gets the fee allowance `granter` has given `grantee`. Errors if there is none.
+    pub async fn query_allowance(
+        &mut self,
+        granter: &str,
+        grantee: &str,
+    ) -> Result<FeeGrant, ChainClientError> {
+        let mut query_client = self.get_query_client::<FeegrantQueryClient>().await?;
+        let request = feegrant::QueryAllowanceRequest {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+        };
+        let response = query_client
+            .allowance(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner();
+
+        response.allowance.ok_or_else(|| {
+            ChainClientError::ModuleQuery(format!(
+                "empty result. {} has no allowance from {}",
+                grantee, granter
+            ))
+        })
+    }
+
+    /// Gets all fee allowances given to `grantee` with optional pagination
+    pub async fn query_allowances(
+        &mut self,
+        grantee: &str,
+        pagination: Option<PageRequest>,
+    ) -> Result<Vec<FeeGrant>, ChainClientError> {
+        let mut query_client = self.get_query_client::<FeegrantQueryClient>().await?;
+        let request = feegrant::QueryAllowancesRequest {
+            grantee: grantee.to_string(),
+            pagination,
+        };
+
+        Ok(query_client
+            .allowances(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .allowances)
+    }
+}
@@ -1,13 +1,23 @@
 //! Queries for the [Slashing module](https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/slashing/v1beta1/query.proto). If you need a query that does not have a method wrapper here, you can use the [`SlashingQueryClient`] directly.
 use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::crypto::ed25519;
+use prost::Message;
+use sha2::{Digest, Sha256};
 use tonic::transport::Channel;
 
-use crate::cosmos_modules::slashing;
+use crate::{
+    account::AccountId,
+    cosmos_modules::slashing,
+    error::{ChainClientError, GrpcError, TxError},
+    tx::{Any, Dec},
+    Timestamp,
+};
 
-use super::QueryClient;
+use super::{ChainClient, PageRequest, QueryClient};
 
 /// The slashing module's query client proto definition
 pub type SlashingQueryClient = slashing::query_client::QueryClient<Channel>;
+pub type SigningInfo = slashing::ValidatorSigningInfo;
 
 #[async_trait]
 impl QueryClient for SlashingQueryClient {
@@ -17,3 +27,189 @@ impl QueryClient for SlashingQueryClient {
         Self::connect(endpoint).await
     }
 }
+
+/// A validator's liveness and jail status
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatorStatus {
+    pub operator_address: String,
+    pub consensus_address: String,
+    pub jailed: bool,
+    /// Time until which the validator can't unjail
+    pub jailed_until: Option<Timestamp>,
+    /// Tombstoned validators can never unjail
+    pub tombstoned: bool,
+    /// Blocks missed in the current signing window
+    pub missed_blocks: i64,
+    pub signed_blocks_window: i64,
+    /// The validator is jailed for downtime once it misses more than this many blocks in the window
+    pub max_missed_blocks: i64,
+}
+
+impl ValidatorStatus {
+    /// Blocks the validator can still miss in the current window before being jailed
+    pub fn missed_blocks_remaining(&self) -> i64 {
+        (self.max_missed_blocks - self.missed_blocks).max(0)
+    }
+}
+
+impl ChainClient {
+    /// Gets the signing info of the validator with consensus address `consensus_address`, e.g. `cosmosvalcons1...`
+    pub async fn query_signing_info(
+        &mut self,
+        consensus_address: &str,
+    ) -> Result<SigningInfo, ChainClientError> {
+        let mut query_client = self.get_query_client::<SlashingQueryClient>().await?;
+        let request = slashing::QuerySigningInfoRequest {
+            cons_address: consensus_address.to_string(),
+        };
+
+        query_client
+            .signing_info(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .val_signing_info
+            .ok_or_else(|| {
+                ChainClientError::ModuleQuery(format!("no signing info for {}", consensus_address))
+            })
+    }
+
+    /// Gets the signing info of all validators with optional pagination
+    pub async fn query_signing_infos(
+        &mut self,
+        pagination: Option<PageRequest>,
+    ) -> Result<Vec<SigningInfo>, ChainClientError> {
+        let mut query_client = self.get_query_client::<SlashingQueryClient>().await?;
+        let request = slashing::QuerySigningInfosRequest { pagination };
+
+        Ok(query_client
+            .signing_infos(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .info)
+    }
+
+    /// Gets the slashing module's parameters
+    pub async fn query_slashing_params(&mut self) -> Result<slashing::Params, ChainClientError> {
+        let mut query_client = self.get_query_client::<SlashingQueryClient>().await?;
+
+        query_client
+            .params(slashing::QueryParamsRequest {})
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .params
+            .ok_or_else(|| ChainClientError::ModuleQuery("no slashing params".to_string()))
+    }
+
+    /// Gets the missed blocks and jail status of the validator with operator address `operator_address`
+    pub async fn query_validator_status(
+        &mut self,
+        operator_address: &str,
+    ) -> Result<ValidatorStatus, ChainClientError> {
+        let validator = self.query_validator(operator_address).await?;
+        let consensus_pubkey = validator.consensus_pubkey.ok_or_else(|| {
+            ChainClientError::ModuleQuery(format!(
+                "validator {} has no consensus public key",
+                operator_address
+            ))
+        })?;
+        let consensus_address = consensus_address(&consensus_pubkey, &self.config.account_prefix)?;
+        let signing_info = self.query_signing_info(&consensus_address).await?;
+        let params = self.query_slashing_params().await?;
+        let min_signed_per_window = String::from_utf8(params.min_signed_per_window)
+            .ok()
+            .and_then(|a| Dec::from_atomics_str(&a).ok())
+            .ok_or_else(|| {
+                TxError::TypeConversion("invalid min_signed_per_window param".to_string())
+            })?;
+
+        Ok(ValidatorStatus {
+            operator_address: operator_address.to_string(),
+            consensus_address,
+            jailed: validator.jailed,
+            jailed_until: signing_info.jailed_until,
+            tombstoned: signing_info.tombstoned,
+            missed_blocks: signing_info.missed_blocks_counter,
+            signed_blocks_window: params.signed_blocks_window,
+            max_missed_blocks: max_missed_blocks(
+                params.signed_blocks_window,
+                min_signed_per_window,
+            ),
+        })
+    }
+}
+
+/// Derives a validator's consensus address, e.g. `cosmosvalcons1...` for the prefix `cosmos`, from its ed25519
+/// consensus public key
+pub fn consensus_address(consensus_pubkey: &Any, prefix: &str) -> Result<String, TxError> {
+    if consensus_pubkey.type_url != "/cosmos.crypto.ed25519.PubKey" {
+        return Err(TxError::TypeConversion(format!(
+            "unsupported consensus public key type {}",
+            consensus_pubkey.type_url
+        )));
+    }
+
+    let key = ed25519::PubKey::decode(&consensus_pubkey.value[..])
+        .map_err(|e| TxError::Serialization(e.to_string()))?;
+    let hash = Sha256::digest(&key.key);
+
+    AccountId::new(&format!("{}valcons", prefix), &hash[..20])
+        .map(|id| id.to_string())
+        .map_err(|e| TxError::Address(e.to_string()))
+}
+
+/// Mirrors the SDK's `window - MinSignedPerWindow(window)`, which rounds half to even
+fn max_missed_blocks(signed_blocks_window: i64, min_signed_per_window: Dec) -> i64 {
+    let scale = 10u128.pow(Dec::DECIMALS);
+    let min_signed = min_signed_per_window
+        .checked_mul_integer(signed_blocks_window.max(0) as u128)
+        .unwrap_or_default();
+    let (integer, fraction) = (min_signed.truncate(), u128::from(min_signed.fraction()));
+    let rounded = if fraction * 2 > scale || (fraction * 2 == scale && integer % 2 == 1) {
+        integer + 1
+    } else {
+        integer
+    };
+
+    signed_blocks_window - rounded as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_consensus_address() {
+        let pubkey = Any {
+            type_url: "/cosmos.crypto.ed25519.PubKey".to_string(),
+            value: ed25519::PubKey { key: vec![1; 32] }.encode_to_vec(),
+        };
+        let address = consensus_address(&pubkey, "cosmos").unwrap();
+        let id = address.parse::<AccountId>().unwrap();
+
+        assert_eq!(id.prefix(), "cosmosvalcons");
+        assert_eq!(
+            hex::encode(id.to_bytes()),
+            "72cd6e8422c407fb6d098690f1130b7ded7ec2f7"
+        );
+        assert!(consensus_address(
+            &Any {
+                type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                value: vec![],
+            },
+            "cosmos"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn computes_max_missed_blocks() {
+        assert_eq!(max_missed_blocks(10000, "0.05".parse().unwrap()), 9500);
+        assert_eq!(max_missed_blocks(100, "0.5".parse().unwrap()), 50);
+        // 0.5 * 5 = 2.5 rounds to 2, 0.5 * 7 = 3.5 rounds to 4
+        assert_eq!(max_missed_blocks(5, "0.5".parse().unwrap()), 3);
+        assert_eq!(max_missed_blocks(7, "0.5".parse().unwrap()), 3);
+    }
+}
@@ -2,9 +2,12 @@
 use async_trait::async_trait;
 use tonic::transport::Channel;
 
-use crate::cosmos_modules::staking;
+use crate::{
+    cosmos_modules::staking,
+    error::{ChainClientError, GrpcError},
+};
 
-use super::QueryClient;
+use super::{ChainClient, QueryClient};
 
 /// The staking module's query client proto definition
 pub type StakingQueryClient = staking::query_client::QueryClient<Channel>;
@@ -17,3 +20,26 @@ impl QueryClient for StakingQueryClient {
         Self::connect(endpoint).await
     }
 }
+
+impl ChainClient {
+    /// Gets the validator with operator address `validator_address`
+    pub async fn query_validator(
+        &mut self,
+        validator_address: &str,
+    ) -> Result<staking::Validator, ChainClientError> {
+        let mut query_client = self.get_query_client::<StakingQueryClient>().await?;
+        let request = staking::QueryValidatorRequest {
+            validator_addr: validator_address.to_string(),
+        };
+
+        query_client
+            .validator(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .validator
+            .ok_or_else(|| {
+                ChainClientError::ModuleQuery(format!("validator {} not found", validator_address))
+            })
+    }
+}
@@ -0,0 +1,19 @@
+//! Queries for the [Tx service](https://github.com/cosmos/cosmos-sdk/blob/main/proto/cosmos/tx/v1beta1/service.proto), such as tx simulation. If you need a query that does not have a method wrapper here, you can use the [`TxServiceClient`] directly.
+use async_trait::async_trait;
+use tonic::transport::Channel;
+
+use cosmos_sdk_proto::cosmos::tx::v1beta1 as tx;
+
+use super::QueryClient;
+
+/// The tx service's client proto definition
+pub type TxServiceClient = tx::service_client::ServiceClient<Channel>;
+
+#[async_trait]
+impl QueryClient for TxServiceClient {
+    type Transport = Channel;
+
+    async fn connect(endpoint: String) -> Result<Self, tonic::transport::Error> {
+        Self::connect(endpoint).await
+    }
+}
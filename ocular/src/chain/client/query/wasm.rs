@@ -0,0 +1,104 @@
+//! Queries for the [CosmWasm module](https://github.com/CosmWasm/wasmd/blob/main/proto/cosmwasm/wasm/v1/query.proto). If you need a query that does not have a method wrapper here, you can use the [`WasmQueryClient`] directly.
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use tonic::transport::Channel;
+
+use crate::{
+    cosmos_modules::wasm,
+    error::{ChainClientError, GrpcError, TxError},
+};
+
+use super::{ChainClient, QueryClient};
+
+/// The wasm module's query client proto definition
+pub type WasmQueryClient = wasm::query_client::QueryClient<Channel>;
+
+#[async_trait]
+impl QueryClient for WasmQueryClient {
+    type Transport = Channel;
+
+    async fn connect(endpoint: String) -> Result<Self, tonic::transport::Error> {
+        Self::connect(endpoint).await
+    }
+}
+
+impl ChainClient {
+    /// Sends `query` to `contract` as JSON and deserializes its JSON response
+    pub async fn query_contract_smart<Q: Serialize, R: DeserializeOwned>(
+        &mut self,
+        contract: &str,
+        query: &Q,
+    ) -> Result<R, ChainClientError> {
+        let mut query_client = self.get_query_client::<WasmQueryClient>().await?;
+        let request = wasm::QuerySmartContractStateRequest {
+            address: contract.to_string(),
+            query_data: serde_json::to_vec(query)
+                .map_err(|e| TxError::Serialization(e.to_string()))?,
+        };
+        let data = query_client
+            .smart_contract_state(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .data;
+
+        Ok(serde_json::from_slice(&data).map_err(|e| TxError::Serialization(e.to_string()))?)
+    }
+
+    /// Gets the raw value stored under `key` in `contract`'s storage. Empty if the key isn't set.
+    pub async fn query_contract_raw(
+        &mut self,
+        contract: &str,
+        key: &[u8],
+    ) -> Result<Vec<u8>, ChainClientError> {
+        let mut query_client = self.get_query_client::<WasmQueryClient>().await?;
+        let request = wasm::QueryRawContractStateRequest {
+            address: contract.to_string(),
+            query_data: key.to_vec(),
+        };
+
+        Ok(query_client
+            .raw_contract_state(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .data)
+    }
+
+    /// Gets the value stored under `key` in `contract`'s storage and deserializes it from JSON, the encoding
+    /// cw-storage-plus uses. `None` if the key isn't set.
+    pub async fn query_contract_raw_json<R: DeserializeOwned>(
+        &mut self,
+        contract: &str,
+        key: &[u8],
+    ) -> Result<Option<R>, ChainClientError> {
+        let data = self.query_contract_raw(contract, key).await?;
+
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            serde_json::from_slice(&data).map_err(|e| TxError::Serialization(e.to_string()))?,
+        ))
+    }
+
+    /// Gets the code id, creator, admin and label of `contract`
+    pub async fn query_contract_info(
+        &mut self,
+        contract: &str,
+    ) -> Result<wasm::ContractInfo, ChainClientError> {
+        let mut query_client = self.get_query_client::<WasmQueryClient>().await?;
+        let request = wasm::QueryContractInfoRequest {
+            address: contract.to_string(),
+        };
+
+        query_client
+            .contract_info(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner()
+            .contract_info
+            .ok_or_else(|| ChainClientError::ModuleQuery(format!("no contract at {}", contract)))
+    }
+}
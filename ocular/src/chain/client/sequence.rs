@@ -0,0 +1,92 @@
+//! Local tracking of signer account sequences, so that several txs can be sent from the same account
+//! without waiting for each one to be committed.
+use std::collections::HashMap;
+
+use crate::account::BaseAccount;
+
+/// The values an account needs in order to sign a tx
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountSequence {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+impl From<&BaseAccount> for AccountSequence {
+    fn from(account: &BaseAccount) -> Self {
+        AccountSequence {
+            account_number: account.account_number,
+            sequence: account.sequence,
+        }
+    }
+}
+
+/// Tracks the next sequence of each signer by address. Entries are populated from chain the first time
+/// an account signs, incremented after each tx that passes CheckTx, and removed when the chain reports
+/// an account sequence mismatch.
+#[derive(Debug, Default)]
+pub struct SequenceManager {
+    accounts: HashMap<String, AccountSequence>,
+}
+
+impl SequenceManager {
+    /// Returns the locally tracked sequence for `address` if there is one
+    pub fn get(&self, address: &str) -> Option<AccountSequence> {
+        self.accounts.get(address).copied()
+    }
+
+    /// Overrides the locally tracked sequence for `address`
+    pub fn set(&mut self, address: &str, sequence: AccountSequence) {
+        self.accounts.insert(address.to_string(), sequence);
+    }
+
+    /// Increments the sequence for `address` if it is being tracked
+    pub fn increment(&mut self, address: &str) {
+        if let Some(s) = self.accounts.get_mut(address) {
+            s.sequence += 1;
+        }
+    }
+
+    /// Stops tracking `address`, causing its sequence to be resynced from chain on next use
+    pub fn reset(&mut self, address: &str) {
+        self.accounts.remove(address);
+    }
+
+    /// Stops tracking all addresses
+    pub fn clear(&mut self) {
+        self.accounts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_sequences() {
+        let mut manager = SequenceManager::default();
+        let address = "cosmos1test";
+
+        assert!(manager.get(address).is_none());
+
+        // incrementing an untracked address is a no-op
+        manager.increment(address);
+        assert!(manager.get(address).is_none());
+
+        manager.set(
+            address,
+            AccountSequence {
+                account_number: 7,
+                sequence: 3,
+            },
+        );
+        manager.increment(address);
+        manager.increment(address);
+
+        let s = manager.get(address).unwrap();
+        assert_eq!(s.account_number, 7);
+        assert_eq!(s.sequence, 5);
+
+        manager.reset(address);
+        assert!(manager.get(address).is_none());
+    }
+}
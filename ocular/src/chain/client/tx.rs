@@ -1,135 +1,635 @@
 use crate::{
     account::AccountInfo,
-    error::{ChainClientError, TxError},
-    tx::TxMetadata,
+    chain::config::TimeoutHeight,
+    error::{AbciFailure, ChainClientError, GrpcError, RpcError, TxError},
+    tx::{
+        calculate_fee, Any, BroadcastMode, SignedTx, SignerData, TxBuilder, TxMetadata, UnsignedTx,
+    },
+};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateRequest;
+use cosmrs::{tendermint::abci::transaction::Hash, tx};
+use std::time::{Duration, Instant};
+use tendermint_rpc::{
+    endpoint::{self, broadcast},
+    Client,
 };
-use cosmrs::tx::{self, Fee, SignDoc, SignerInfo};
-use std::fs::File;
-use std::io::prelude::*;
-use std::os::unix::fs::PermissionsExt;
 
-use super::ChainClient;
+use super::{query::TxServiceClient, sequence::AccountSequence, tx_log::TxLogRecord, ChainClient};
 
-pub use self::{authz::*, bank::*};
-pub type BroadcastCommitResponse = tendermint_rpc::endpoint::broadcast::tx_commit::Response;
+#[cfg(feature = "cosmwasm")]
+pub use self::wasm::*;
+pub use self::{
+    authz::*, bank::*, distribution::*, feegrant::*, gov::*, ibc::*, slashing::*, staking::*,
+    vesting::*,
+};
+pub type BroadcastCommitResponse = broadcast::tx_commit::Response;
+pub type GasInfo = cosmos_sdk_proto::cosmos::base::abci::v1beta1::GasInfo;
 
 pub mod authz;
 pub mod bank;
+pub mod distribution;
+pub mod feegrant;
+pub mod gov;
+pub mod ibc;
+pub mod slashing;
+pub mod staking;
+pub mod vesting;
+#[cfg(feature = "cosmwasm")]
+pub mod wasm;
+
+/// How often [`ChainClient::wait_for_tx`] polls the node
+const TX_POLLING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The outcome of CheckTx or DeliverTx for a tx.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TxResult {
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+}
+
+impl TxResult {
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+}
+
+impl From<broadcast::tx_commit::TxResult> for TxResult {
+    fn from(result: broadcast::tx_commit::TxResult) -> Self {
+        TxResult {
+            code: result.code.value(),
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+            gas_wanted: result.gas_wanted.value(),
+            gas_used: result.gas_used.value(),
+        }
+    }
+}
+
+impl From<cosmrs::tendermint::abci::DeliverTx> for TxResult {
+    fn from(result: cosmrs::tendermint::abci::DeliverTx) -> Self {
+        TxResult {
+            code: result.code.value(),
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+            gas_wanted: result.gas_wanted.value(),
+            gas_used: result.gas_used.value(),
+        }
+    }
+}
+
+/// A broadcast response that is the same regardless of the [`BroadcastMode`] used.
+#[derive(Clone, Debug)]
+pub struct TxResponse {
+    pub hash: Hash,
+    /// Height of the block the tx was included in. `None` if it has not been committed yet.
+    pub height: Option<u64>,
+    /// `None` if the tx was broadcast with [`BroadcastMode::Async`]
+    pub check_tx: Option<TxResult>,
+    /// `None` if the tx has not been committed yet.
+    pub deliver_tx: Option<TxResult>,
+}
 
-/// Where tx logs are stored.
-const TX_LOGGING_DIR: &str = "/.ocular/logs/txs";
-/// Unix permissions for dir
-const TX_LOGGING_DIR_PERMISSIONS: u32 = 0o700;
+impl TxResponse {
+    /// Returns a typed [`TxError`] if the tx failed CheckTx or DeliverTx.
+    pub fn check_result(&self) -> Result<(), TxError> {
+        for result in [&self.check_tx, &self.deliver_tx].into_iter().flatten() {
+            if result.is_err() {
+                return Err(TxError::from_abci(AbciFailure {
+                    hash: self.hash.to_string(),
+                    codespace: result.codespace.clone(),
+                    code: result.code,
+                    log: result.log.clone(),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the first `key` attribute of an event of type `event_type` in the DeliverTx log, e.g. the
+    /// `code_id` of a `store_code` event. `None` if the tx hasn't been committed or has no such attribute.
+    pub fn find_event_attribute(&self, event_type: &str, key: &str) -> Option<String> {
+        let log: serde_json::Value = serde_json::from_str(&self.deliver_tx.as_ref()?.log).ok()?;
+
+        log.as_array()?
+            .iter()
+            .filter_map(|msg_log| msg_log["events"].as_array())
+            .flatten()
+            .filter(|event| event["type"] == event_type)
+            .filter_map(|event| event["attributes"].as_array())
+            .flatten()
+            .find(|attribute| attribute["key"] == key)
+            .and_then(|attribute| attribute["value"].as_str())
+            .map(String::from)
+    }
+}
+
+impl From<BroadcastCommitResponse> for TxResponse {
+    fn from(response: BroadcastCommitResponse) -> Self {
+        TxResponse {
+            hash: response.hash,
+            height: Some(response.height.value()),
+            check_tx: Some(response.check_tx.into()),
+            deliver_tx: Some(response.deliver_tx.into()),
+        }
+    }
+}
+
+impl From<broadcast::tx_sync::Response> for TxResponse {
+    fn from(response: broadcast::tx_sync::Response) -> Self {
+        TxResponse {
+            hash: response.hash,
+            height: None,
+            check_tx: Some(TxResult {
+                code: response.code.value(),
+                log: response.log.to_string(),
+                ..Default::default()
+            }),
+            deliver_tx: None,
+        }
+    }
+}
+
+impl From<endpoint::tx::Response> for TxResponse {
+    fn from(response: endpoint::tx::Response) -> Self {
+        TxResponse {
+            hash: response.hash,
+            height: Some(response.height.value()),
+            check_tx: None,
+            deliver_tx: Some(response.tx_result.into()),
+        }
+    }
+}
 
 impl ChainClient {
+    /// Returns metadata with no fee and a 0 gas limit, meaning gas will be estimated by simulation when the tx
+    /// is signed and the fee computed from the config's `gas_prices`. The timeout height is determined by the
+    /// config's `timeout_height`.
     pub async fn get_basic_tx_metadata(&self) -> Result<TxMetadata, ChainClientError> {
-        let current_height = self.query_latest_height().await?;
-        let timeout_height: u32 = (current_height + 30) as u32;
-        let fee = self.config.default_fee.clone();
+        let timeout_height = self.get_timeout_height(self.config.timeout_height).await?;
 
         Ok(TxMetadata {
-            fee,
+            fee: Vec::new(),
             fee_payer: None,
             fee_granter: None,
-            gas_limit: 200000,
+            gas_limit: 0,
             timeout_height,
             memo: String::default(),
+            ..Default::default()
         })
     }
 
-    /// Helper method for signing and broadcasting messages.
+    /// Resolves a [`TimeoutHeight`] to the height set on a tx, where 0 means no timeout. Only
+    /// [`TimeoutHeight::Delta`] needs the latest height, which is cached by the client's `height_cache`.
+    pub async fn get_timeout_height(
+        &self,
+        timeout: TimeoutHeight,
+    ) -> Result<u32, ChainClientError> {
+        let height = match timeout {
+            TimeoutHeight::Delta(delta) => {
+                let latest_height = self.get_latest_height().await?;
+
+                latest_height.checked_add(delta).ok_or_else(|| {
+                    TxError::TypeConversion(format!(
+                        "timeout height delta {} overflows latest height {}",
+                        delta, latest_height
+                    ))
+                })?
+            }
+            TimeoutHeight::Absolute(height) => height,
+            TimeoutHeight::None => 0,
+        };
+
+        u32::try_from(height).map_err(|_| {
+            TxError::TypeConversion(format!("timeout height {} is too large", height)).into()
+        })
+    }
+
+    /// Returns the latest block height from the client's `height_cache`, querying it if the cached height expired
+    pub async fn get_latest_height(&self) -> Result<u64, ChainClientError> {
+        if let Some(height) = self.height_cache.get() {
+            return Ok(height);
+        }
+
+        let height = self.query_latest_height().await?;
+        self.height_cache.set(height);
+
+        Ok(height)
+    }
+
+    /// Simulates the transaction against the chain's current state and returns the estimated gas info.
+    /// Gas used is not adjusted by the config's `gas_adjustment`.
+    pub async fn simulate_tx(
+        &mut self,
+        sender: &AccountInfo,
+        tx_body: &tx::Body,
+        tx_metadata: &TxMetadata,
+    ) -> Result<GasInfo, ChainClientError> {
+        let address = sender.address(&self.config.account_prefix)?;
+        let sequence = self.get_account_sequence(&address).await?;
+
+        self.simulate_tx_for_sequence(sender, sequence, tx_body, tx_metadata)
+            .await
+    }
+
+    async fn simulate_tx_for_sequence(
+        &mut self,
+        sender: &AccountInfo,
+        sequence: AccountSequence,
+        tx_body: &tx::Body,
+        tx_metadata: &TxMetadata,
+    ) -> Result<GasInfo, ChainClientError> {
+        // Signatures are not verified during simulation, but the tx must still be well formed.
+        let tx_bytes = self
+            .sign_tx(sender, sequence, tx_body.clone(), tx_metadata)?
+            .to_bytes();
+
+        let mut query_client = self.get_query_client::<TxServiceClient>().await?;
+        #[allow(deprecated)]
+        let request = SimulateRequest { tx: None, tx_bytes };
+        let response = query_client
+            .simulate(request)
+            .await
+            .map_err(GrpcError::Request)?
+            .into_inner();
+
+        match response.gas_info {
+            Some(gas_info) => Ok(gas_info),
+            None => Err(TxError::Simulation("response contained no gas info".to_string()).into()),
+        }
+    }
+
+    /// Gets the account number and next sequence for `address`, preferring the locally tracked
+    /// sequence over querying the chain.
+    pub async fn get_account_sequence(
+        &mut self,
+        address: &str,
+    ) -> Result<AccountSequence, ChainClientError> {
+        if let Some(sequence) = self.sequence_manager.get(address) {
+            return Ok(sequence);
+        }
+
+        let account = self.query_account(address).await?;
+        let sequence = AccountSequence::from(account.base_account());
+        self.sequence_manager.set(address, sequence);
+
+        Ok(sequence)
+    }
+
+    /// Returns a [`TxBuilder`] for the client's chain. Msgs from helpers like [`ChainClient::send_msg`] and
+    /// [`ChainClient::grant_generic_authorization_msg`] can be added to it and then sent atomically in a single tx
+    /// with [`ChainClient::sign_and_send_tx`].
+    pub fn tx_builder(&self) -> TxBuilder {
+        TxBuilder::new(SignerData {
+            chain_id: self.config.chain_id.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// Signs all msgs in `builder` once and broadcasts them as a single tx. The sender's account number and sequence
+    /// are determined by the client. If the builder has no tx metadata, [`ChainClient::get_basic_tx_metadata`] is used.
+    pub async fn sign_and_send_tx(
+        &mut self,
+        sender: &AccountInfo,
+        builder: TxBuilder,
+    ) -> Result<TxResponse, ChainClientError> {
+        let (msgs, tx_metadata) = builder.into_parts();
+
+        self.sign_and_send_msgs(sender, msgs, tx_metadata).await
+    }
+
+    /// Signs and broadcasts `msgs` in a single tx. If `tx_metadata` is `None`, [`ChainClient::get_basic_tx_metadata`]
+    /// is used.
+    pub async fn sign_and_send_msgs(
+        &mut self,
+        sender: &AccountInfo,
+        msgs: Vec<Any>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let tx_metadata = match tx_metadata {
+            Some(tm) => tm,
+            None => self.get_basic_tx_metadata().await?,
+        };
+        let tx_body = tx::Body::new(msgs, &tx_metadata.memo, tx_metadata.timeout_height);
+
+        self.sign_and_send_msg(sender, tx_body, tx_metadata).await
+    }
+
+    /// Helper method for signing and broadcasting messages. If the gas limit in `tx_metadata` is 0, the tx
+    /// will first be simulated to estimate the gas limit. If the fee is empty, it is computed from the config's
+    /// `gas_prices`, and the tx is not sent if there are none.
+    ///
+    /// The sender's sequence is tracked locally between calls. If the chain reports an account sequence
+    /// mismatch, the sequence is resynced from chain and the tx is retried once.
+    ///
+    /// Returns a [`TxError`] if the tx fails CheckTx or DeliverTx, unless `tx_metadata.allow_failed_tx` is set.
     pub async fn sign_and_send_msg(
         &mut self,
         sender: &AccountInfo,
         tx_body: tx::Body,
         tx_metadata: TxMetadata,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
-        let account = self
-            .query_account(&sender.address(&self.config.account_prefix)?)
-            .await?;
+    ) -> Result<TxResponse, ChainClientError> {
+        let address = sender.address(&self.config.account_prefix)?;
+        let mut result = self
+            .try_sign_and_send_msg(sender, &address, tx_body.clone(), tx_metadata.clone())
+            .await;
 
-        // Create signer info.
-        let signer_info = SignerInfo::single_direct(Some(sender.public_key()), account.sequence);
-
-        // Compute auth info from signer info by associating a fee.
-        let auth_info = signer_info.auth_info(Fee {
-            amount: vec![tx_metadata.fee.try_into()?],
-            gas_limit: tx_metadata.gas_limit.into(),
-            payer: tx_metadata.fee_payer,
-            granter: tx_metadata.fee_granter,
-        });
-        let chain_id = &cosmrs::tendermint::chain::Id::try_from(self.config.chain_id.clone())?;
-
-        // Create doc to be signed
-        let sign_doc = match SignDoc::new(&tx_body, &auth_info, chain_id, account.account_number) {
-            Ok(doc) => doc,
-            Err(err) => return Err(TxError::TypeConversion(err.to_string()).into()),
-        };
+        if is_sequence_mismatch(&result) {
+            self.sequence_manager.reset(&address);
+            result = self
+                .try_sign_and_send_msg(sender, &address, tx_body, tx_metadata.clone())
+                .await;
+        }
 
-        // Create raw signed transaction.
-        let tx_signed = match sign_doc.sign(sender.private_key()) {
-            Ok(raw) => raw,
-            Err(err) => return Err(TxError::Signing(err.to_string()).into()),
-        };
+        let response = result?;
+
+        if !tx_metadata.allow_failed_tx {
+            response.check_result()?;
+        }
+
+        // Finally return.
+        Ok(response)
+    }
+
+    async fn try_sign_and_send_msg(
+        &mut self,
+        sender: &AccountInfo,
+        address: &str,
+        tx_body: tx::Body,
+        mut tx_metadata: TxMetadata,
+    ) -> Result<TxResponse, ChainClientError> {
+        let sequence = self.get_account_sequence(address).await?;
+
+        if tx_metadata.gas_limit == 0 {
+            let gas_info = self
+                .simulate_tx_for_sequence(sender, sequence, &tx_body, &tx_metadata)
+                .await?;
+            tx_metadata.gas_limit = adjust_gas(gas_info.gas_used, self.config.gas_adjustment);
+        }
+
+        if tx_metadata.fee.is_empty() {
+            tx_metadata.fee = calculate_fee(tx_metadata.gas_limit, &self.config.gas_prices)?;
+        }
+
+        let tx_bytes = self
+            .sign_tx(sender, sequence, tx_body, &tx_metadata)?
+            .to_bytes();
 
         // Broadcast transaction
-        let response = match tx_signed.broadcast_commit(&self.rpc_client).await {
-            Ok(response) => response,
-            Err(err) => return Err(TxError::Broadcast(err.to_string()).into()),
-        };
+        let response = self
+            .broadcast_tx_bytes(tx_bytes.clone(), tx_metadata.broadcast_mode)
+            .await?;
+
+        // The sequence is incremented once a tx passes CheckTx, regardless of the DeliverTx result. Async
+        // broadcasts don't report CheckTx so we assume they passed.
+        if response.check_tx.as_ref().map_or(true, TxResult::is_ok) {
+            self.sequence_manager.increment(address);
+        }
+
+        self.log_tx(&tx_bytes, &response);
+
+        Ok(response)
+    }
+
+    /// Appends the tx to the client's [`TxLog`], if it has one. Logging is best effort: the tx was already
+    /// broadcast, so a failure to log it is only reported as a warning.
+    fn log_tx(&self, tx_bytes: &[u8], response: &TxResponse) {
+        if let Some(tx_log) = &self.tx_log {
+            let result = TxLogRecord::new(
+                &self.config.chain_id,
+                &self.config.account_prefix,
+                tx_bytes,
+                response,
+            )
+            .and_then(|record| tx_log.append(&record));
+
+            if let Err(err) = result {
+                eprintln!("Warning: failed to log tx {}: {}", response.hash, err);
+            }
+        }
+    }
+
+    /// Polls the node for a tx with the given hash until it is found in a block or `timeout` elapses.
+    /// Useful for confirming txs broadcast with [`BroadcastMode::Sync`] or [`BroadcastMode::Async`]. Errors other
+    /// than the tx not being found yet, such as connection failures, are returned right away.
+    pub async fn wait_for_tx(
+        &self,
+        hash: Hash,
+        timeout: Duration,
+    ) -> Result<TxResponse, ChainClientError> {
+        let start = Instant::now();
+
+        loop {
+            match self.rpc_client.tx(hash, false).await {
+                Ok(response) => return Ok(response.into()),
+                Err(err) if is_tx_not_found(&err.to_string()) => (),
+                Err(err) => return Err(RpcError::TendermintStatus(err).into()),
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(TxError::Timeout(format!(
+                    "tx {} was not found within {:?}",
+                    hash, timeout
+                ))
+                .into());
+            }
+
+            tokio::time::sleep(TX_POLLING_INTERVAL).await;
+        }
+    }
 
-        // Store tx in logs with timestamp id in ~/.ocular/logs/txs
-        let save_path = dirs::home_dir()
-            .unwrap()
-            .into_os_string()
-            .into_string()
-            .expect("Could not obtain home directory.")
-            + TX_LOGGING_DIR;
-
-        let save_file = save_path.clone()
-            + "/"
-            + &std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-                .to_string()
-            + &std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .subsec_nanos()
-                .to_string()
-            + ".txt";
-
-        let st = std::path::Path::new(&save_path).metadata();
-
-        // Create dir if doesn't exist
-        if st.is_err() {
-            match std::fs::create_dir_all(&save_path) {
-                Ok(_res) => _res,
-                Err(err) => return Err(TxError::Logging(err.to_string()).into()),
-            };
-        }
-
-        #[cfg(unix)]
-        match std::fs::set_permissions(
-            &save_path,
-            std::fs::Permissions::from_mode(TX_LOGGING_DIR_PERMISSIONS),
-        ) {
-            Ok(_res) => _res,
-            Err(err) => return Err(TxError::Logging(err.to_string()).into()),
+    /// Broadcasts the protobuf encoded bytes of a tx that was signed separately, for example with
+    /// [`UnsignedTx::sign`] on an offline machine. The response is returned as-is; use [`TxResponse::check_result`]
+    /// to get a typed error if the tx failed.
+    pub async fn broadcast_signed_tx(
+        &self,
+        tx_bytes: Vec<u8>,
+        mode: BroadcastMode,
+    ) -> Result<TxResponse, ChainClientError> {
+        let response = self.broadcast_tx_bytes(tx_bytes.clone(), mode).await?;
+
+        self.log_tx(&tx_bytes, &response);
+
+        Ok(response)
+    }
+
+    async fn broadcast_tx_bytes(
+        &self,
+        tx_bytes: Vec<u8>,
+        mode: BroadcastMode,
+    ) -> Result<TxResponse, ChainClientError> {
+        let response: Result<TxResponse, tendermint_rpc::Error> = match mode {
+            BroadcastMode::Async => self
+                .rpc_client
+                .broadcast_tx_async(tx_bytes.into())
+                .await
+                .map(|r| TxResponse {
+                    hash: r.hash,
+                    height: None,
+                    check_tx: None,
+                    deliver_tx: None,
+                }),
+            BroadcastMode::Sync => self
+                .rpc_client
+                .broadcast_tx_sync(tx_bytes.into())
+                .await
+                .map(TxResponse::from),
+            BroadcastMode::Commit => self
+                .rpc_client
+                .broadcast_tx_commit(tx_bytes.into())
+                .await
+                .map(TxResponse::from),
         };
 
-        let mut file = match File::create(save_file) {
-            Ok(res) => res,
-            Err(err) => return Err(TxError::Logging(err.to_string()).into()),
+        let response = response.map_err(|err| TxError::Broadcast(err.to_string()))?;
+
+        Ok(response)
+    }
+
+    fn sign_tx(
+        &self,
+        sender: &AccountInfo,
+        sequence: AccountSequence,
+        tx_body: tx::Body,
+        tx_metadata: &TxMetadata,
+    ) -> Result<SignedTx, ChainClientError> {
+        let signer_data = SignerData {
+            chain_id: self.config.chain_id.clone(),
+            account_number: sequence.account_number,
+            sequence: sequence.sequence,
         };
+        let unsigned_tx =
+            UnsignedTx::new(&tx_body, sender.public_key(), &signer_data, tx_metadata)?;
+
+        Ok(unsigned_tx.sign(sender)?)
+    }
+}
+
+fn is_sequence_mismatch(result: &Result<TxResponse, ChainClientError>) -> bool {
+    match result {
+        Ok(response) => {
+            matches!(response.check_result(), Err(TxError::SequenceMismatch(_)))
+                || response.check_tx.as_ref().map_or(false, |r| {
+                    r.is_err() && r.log.contains("account sequence mismatch")
+                })
+        }
+        // Simulation runs the same ante handler checks, but reports failures as gRPC errors
+        Err(ChainClientError::Tx(TxError::SequenceMismatch(_))) => true,
+        Err(err) => err.to_string().contains("account sequence mismatch"),
+    }
+}
+
+/// Tendermint reports txs that aren't indexed yet as `tx (<hash>) not found`
+fn is_tx_not_found(error: &str) -> bool {
+    error.contains("tx (") && error.contains(") not found")
+}
 
-        match file.write_all(format!("{:#?}", response).as_bytes()) {
-            Ok(res) => res,
-            Err(err) => return Err(TxError::Logging(err.to_string()).into()),
+/// Multiplies the simulated gas usage by the gas adjustment. Adjustments less than 1.0 are ignored.
+pub fn adjust_gas(gas_used: u64, gas_adjustment: f64) -> u64 {
+    let gas_adjustment = if gas_adjustment < 1.0 {
+        1.0
+    } else {
+        gas_adjustment
+    };
+
+    (gas_used as f64 * gas_adjustment).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjusts_gas() {
+        assert_eq!(adjust_gas(100000, 1.2), 120000);
+        assert_eq!(adjust_gas(100001, 1.5), 150002);
+        assert_eq!(adjust_gas(100000, 0.0), 100000);
+        assert_eq!(adjust_gas(0, 1.2), 0);
+    }
+
+    #[test]
+    fn detects_tx_not_found() {
+        assert!(is_tx_not_found(
+            "Internal error: tx (5A3E6A3B4D6F8C2D1E0F) not found"
+        ));
+        assert!(!is_tx_not_found("connection refused"));
+        assert!(!is_tx_not_found("404 Not Found"));
+    }
+
+    fn sync_response(code: u32, log: &str) -> broadcast::tx_sync::Response {
+        broadcast::tx_sync::Response {
+            code: code.into(),
+            data: Vec::<u8>::new().into(),
+            log: log.into(),
+            hash: Hash::new([0; 32]),
+        }
+    }
+
+    #[test]
+    fn maps_sync_check_tx_failures() {
+        let response = TxResponse::from(sync_response(
+            32,
+            "account sequence mismatch, expected 5, got 4: incorrect account sequence",
+        ));
+
+        assert!(matches!(
+            response.check_result(),
+            Err(TxError::SequenceMismatch(_))
+        ));
+        assert!(TxResponse::from(sync_response(0, "[]"))
+            .check_result()
+            .is_ok());
+    }
+
+    #[test]
+    fn detects_sync_sequence_mismatch() {
+        let log = "account sequence mismatch, expected 5, got 4: incorrect account sequence";
+
+        assert!(is_sequence_mismatch(&Ok(sync_response(32, log).into())));
+        // matched by the log even if the code isn't mapped
+        assert!(is_sequence_mismatch(&Ok(sync_response(1, log).into())));
+        assert!(!is_sequence_mismatch(&Ok(sync_response(0, "[]").into())));
+        assert!(!is_sequence_mismatch(&Ok(sync_response(
+            5,
+            "insufficient funds"
+        )
+        .into())));
+    }
+
+    #[test]
+    fn finds_event_attributes() {
+        let response = TxResponse {
+            hash: Hash::new([0; 32]),
+            height: Some(100),
+            check_tx: None,
+            deliver_tx: Some(TxResult {
+                log: r#"[{"msg_index":0,"events":[{"type":"message","attributes":[{"key":"action","value":"/cosmwasm.wasm.v1.MsgStoreCode"}]},{"type":"store_code","attributes":[{"key":"code_id","value":"42"}]}]}]"#.to_string(),
+                ..Default::default()
+            }),
         };
 
-        // Finally return.
-        Ok(response)
+        assert_eq!(
+            response.find_event_attribute("store_code", "code_id"),
+            Some("42".to_string())
+        );
+        assert_eq!(
+            response.find_event_attribute("store_code", "checksum"),
+            None
+        );
+        assert_eq!(
+            TxResponse {
+                deliver_tx: None,
+                ..response
+            }
+            .find_event_attribute("store_code", "code_id"),
+            None
+        );
     }
 }
@@ -4,90 +4,335 @@ use crate::{
     account::AccountInfo,
     cosmos_modules::{
         authz::*,
-        feegrant::{BasicAllowance, MsgGrantAllowance},
+        bank::SendAuthorization,
+        staking::{
+            stake_authorization::{Policy, Validators},
+            AuthorizationType, StakeAuthorization,
+        },
     },
-    error::ChainClientError,
-    tx::{Any, TxMetadata},
+    error::{ChainClientError, TxError},
+    tx::{Any, Coin, TxMetadata},
     Timestamp,
 };
-use cosmrs::{tx, AccountId};
+use cosmrs::AccountId;
 use prost::Message;
 
-use super::{BroadcastCommitResponse, ChainClient};
+use super::{ChainClient, TxResponse};
+
+const GENERIC_AUTHORIZATION_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+const SEND_AUTHORIZATION_URL: &str = "/cosmos.bank.v1beta1.SendAuthorization";
+const STAKE_AUTHORIZATION_URL: &str = "/cosmos.staking.v1beta1.StakeAuthorization";
+/// Grants without an explicit expiration expire after a year
+const DEFAULT_GRANT_DURATION: Duration = Duration::from_secs(31536000);
+
+/// An authorization a granter gives a grantee to execute msgs on its behalf
+#[derive(Clone, Debug, PartialEq)]
+pub enum Authorization {
+    /// Allows any msg of type `msg`
+    Generic { msg: String },
+    /// Allows MsgSends up to `spend_limit`
+    Send { spend_limit: Vec<Coin> },
+    /// Allows delegating, undelegating or redelegating up to `max_tokens` (unlimited if `None`) to the allowed, or
+    /// not denied, validators
+    Stake {
+        max_tokens: Option<Coin>,
+        validators: StakeValidators,
+        authorization_type: AuthorizationType,
+    },
+    /// An authorization type ocular doesn't know about
+    Unknown(Any),
+}
+
+/// The validators a [`Authorization::Stake`] applies to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StakeValidators {
+    AllowList(Vec<String>),
+    DenyList(Vec<String>),
+}
+
+impl Authorization {
+    pub fn generic(msg_type_url: &str) -> Authorization {
+        Authorization::Generic {
+            msg: msg_type_url.to_string(),
+        }
+    }
+
+    pub fn send(spend_limit: Vec<Coin>) -> Authorization {
+        Authorization::Send { spend_limit }
+    }
+
+    pub fn stake(
+        authorization_type: AuthorizationType,
+        validators: StakeValidators,
+        max_tokens: Option<Coin>,
+    ) -> Authorization {
+        Authorization::Stake {
+            max_tokens,
+            validators,
+            authorization_type,
+        }
+    }
+
+    /// The type URL of the msgs this authorization allows, which is also what revoking it requires
+    pub fn msg_type_url(&self) -> Option<String> {
+        match self {
+            Authorization::Generic { msg } => Some(msg.clone()),
+            Authorization::Send { .. } => Some("/cosmos.bank.v1beta1.MsgSend".to_string()),
+            Authorization::Stake {
+                authorization_type, ..
+            } => match authorization_type {
+                AuthorizationType::Delegate => Some("/cosmos.staking.v1beta1.MsgDelegate"),
+                AuthorizationType::Undelegate => Some("/cosmos.staking.v1beta1.MsgUndelegate"),
+                AuthorizationType::Redelegate => Some("/cosmos.staking.v1beta1.MsgBeginRedelegate"),
+                AuthorizationType::Unspecified => None,
+            }
+            .map(String::from),
+            Authorization::Unknown(_) => None,
+        }
+    }
+
+    pub fn to_any(&self) -> Any {
+        match self {
+            Authorization::Generic { msg } => Any {
+                type_url: GENERIC_AUTHORIZATION_URL.to_string(),
+                value: GenericAuthorization { msg: msg.clone() }.encode_to_vec(),
+            },
+            Authorization::Send { spend_limit } => Any {
+                type_url: SEND_AUTHORIZATION_URL.to_string(),
+                value: SendAuthorization {
+                    spend_limit: spend_limit.iter().cloned().map(Into::into).collect(),
+                }
+                .encode_to_vec(),
+            },
+            Authorization::Stake {
+                max_tokens,
+                validators,
+                authorization_type,
+            } => Any {
+                type_url: STAKE_AUTHORIZATION_URL.to_string(),
+                value: StakeAuthorization {
+                    max_tokens: max_tokens.clone().map(Into::into),
+                    authorization_type: *authorization_type as i32,
+                    validators: Some(match validators {
+                        StakeValidators::AllowList(address) => Policy::AllowList(Validators {
+                            address: address.clone(),
+                        }),
+                        StakeValidators::DenyList(address) => Policy::DenyList(Validators {
+                            address: address.clone(),
+                        }),
+                    }),
+                }
+                .encode_to_vec(),
+            },
+            Authorization::Unknown(any) => any.clone(),
+        }
+    }
+}
+
+impl TryFrom<Any> for Authorization {
+    type Error = TxError;
+
+    fn try_from(any: Any) -> Result<Authorization, Self::Error> {
+        Authorization::try_from(&any)
+    }
+}
+
+impl TryFrom<&Any> for Authorization {
+    type Error = TxError;
+
+    fn try_from(any: &Any) -> Result<Authorization, Self::Error> {
+        match any.type_url.as_str() {
+            GENERIC_AUTHORIZATION_URL => {
+                let authorization = GenericAuthorization::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+
+                Ok(Authorization::Generic {
+                    msg: authorization.msg,
+                })
+            }
+            SEND_AUTHORIZATION_URL => {
+                let authorization = SendAuthorization::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+
+                Ok(Authorization::Send {
+                    spend_limit: authorization
+                        .spend_limit
+                        .into_iter()
+                        .map(Coin::try_from)
+                        .collect::<Result<Vec<Coin>, _>>()?,
+                })
+            }
+            STAKE_AUTHORIZATION_URL => {
+                let authorization = StakeAuthorization::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+                let validators = match authorization.validators {
+                    Some(Policy::AllowList(v)) => StakeValidators::AllowList(v.address),
+                    Some(Policy::DenyList(v)) => StakeValidators::DenyList(v.address),
+                    None => {
+                        return Err(TxError::TypeConversion(
+                            "StakeAuthorization has no validators".to_string(),
+                        ))
+                    }
+                };
+
+                Ok(Authorization::Stake {
+                    max_tokens: authorization.max_tokens.map(Coin::try_from).transpose()?,
+                    validators,
+                    authorization_type: AuthorizationType::from_i32(
+                        authorization.authorization_type,
+                    )
+                    .ok_or_else(|| {
+                        TxError::TypeConversion(format!(
+                            "invalid authorization type {}",
+                            authorization.authorization_type
+                        ))
+                    })?,
+                })
+            }
+            _ => Ok(Authorization::Unknown(any.clone())),
+        }
+    }
+}
 
 impl ChainClient {
-    // Grant Authorization
-    // TODO: support other types of authorization grants other than GenericAuthorization for send messages.
-    pub async fn grant_generic_authorization(
+    /// Grants `grantee` `authorization` to execute msgs on the granter's behalf until `expiration`, which defaults to
+    /// a year from now
+    pub async fn grant_authorization(
         &mut self,
         granter: &AccountInfo,
         grantee: AccountId,
-        message: &str,
-        expiration_timestamp: Option<Timestamp>,
+        authorization: Authorization,
+        expiration: Option<Timestamp>,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
-        let expiration: Timestamp = match expiration_timestamp {
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any = self.grant_authorization_msg(granter, grantee, &authorization, expiration)?;
+
+        self.sign_and_send_msgs(granter, vec![msg_any], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgGrant for use with a [`crate::tx::TxBuilder`]
+    pub fn grant_authorization_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        authorization: &Authorization,
+        expiration: Option<Timestamp>,
+    ) -> Result<Any, ChainClientError> {
+        let expiration = match expiration {
             Some(exp) => exp,
-            None => {
-                // defaults to 24 hour expiration
-                let timestamp = SystemTime::now()
-                    .checked_add(Duration::from_secs(31536000))
-                    .unwrap();
-                Timestamp::from(timestamp)
-            }
+            None => Timestamp::from(
+                SystemTime::now()
+                    .checked_add(DEFAULT_GRANT_DURATION)
+                    .unwrap(),
+            ),
         };
-
         let msg = MsgGrant {
             granter: granter.address(&self.config.account_prefix)?,
             grantee: grantee.to_string(),
             grant: Some(Grant {
-                authorization: Some(Any {
-                    type_url: String::from("/cosmos.authz.v1beta1.GenericAuthorization"),
-                    value: GenericAuthorization {
-                        msg: String::from(message),
-                    }
-                    .encode_to_vec(),
-                }),
+                authorization: Some(authorization.to_any()),
                 expiration: Some(expiration),
             }),
         };
-        let msg_any = Any {
+
+        Ok(Any {
             type_url: String::from("/cosmos.authz.v1beta1.MsgGrant"),
             value: msg.encode_to_vec(),
-        };
-        let tx_metadata = match tx_metadata {
-            Some(tm) => tm,
-            None => self.get_basic_tx_metadata().await?,
-        };
-        let tx_body = tx::Body::new(vec![msg_any], &tx_metadata.memo, tx_metadata.timeout_height);
+        })
+    }
 
-        self.sign_and_send_msg(granter, tx_body, tx_metadata).await
+    /// Grants `grantee` a GenericAuthorization for msgs of type `message`
+    pub async fn grant_generic_authorization(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        message: &str,
+        expiration_timestamp: Option<Timestamp>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        self.grant_authorization(
+            granter,
+            grantee,
+            Authorization::generic(message),
+            expiration_timestamp,
+            tx_metadata,
+        )
+        .await
     }
 
-    // Revoke Authorization
-    // TODO: support other types of authorization revokes other than send messages.
-    pub async fn revoke_send_authorization(
+    /// Creates a MsgGrant with a GenericAuthorization for use with a [`crate::tx::TxBuilder`]
+    pub fn grant_generic_authorization_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        message: &str,
+        expiration_timestamp: Option<Timestamp>,
+    ) -> Result<Any, ChainClientError> {
+        self.grant_authorization_msg(
+            granter,
+            grantee,
+            &Authorization::generic(message),
+            expiration_timestamp,
+        )
+    }
+
+    /// Revokes the authorization for msgs of type `msg_type_url` the granter has given `grantee`
+    pub async fn revoke_authorization(
         &mut self,
         granter: &AccountInfo,
         grantee: AccountId,
+        msg_type_url: &str,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any = self.revoke_authorization_msg(granter, grantee, msg_type_url)?;
+
+        self.sign_and_send_msgs(granter, vec![msg_any], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgRevoke for use with a [`crate::tx::TxBuilder`]
+    pub fn revoke_authorization_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        msg_type_url: &str,
+    ) -> Result<Any, ChainClientError> {
         let msg = MsgRevoke {
             granter: granter.address(&self.config.account_prefix)?,
             grantee: grantee.to_string(),
-            msg_type_url: String::from("/cosmos.bank.v1beta1.MsgSend"),
+            msg_type_url: msg_type_url.to_string(),
         };
-        let msg_any = Any {
+
+        Ok(Any {
             type_url: String::from("/cosmos.authz.v1beta1.MsgRevoke"),
             value: msg.encode_to_vec(),
-        };
-        let tx_metadata = match tx_metadata {
-            Some(tm) => tm,
-            None => self.get_basic_tx_metadata().await?,
-        };
-        let tx_body = tx::Body::new(vec![msg_any], &tx_metadata.memo, tx_metadata.timeout_height);
+        })
+    }
+
+    /// Revokes the authorization for MsgSend the granter has given `grantee`
+    pub async fn revoke_send_authorization(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        self.revoke_authorization(
+            granter,
+            grantee,
+            "/cosmos.bank.v1beta1.MsgSend",
+            tx_metadata,
+        )
+        .await
+    }
 
-        self.sign_and_send_msg(granter, tx_body, tx_metadata).await
+    /// Creates a MsgRevoke of a MsgSend authorization for use with a [`crate::tx::TxBuilder`]
+    pub fn revoke_send_authorization_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+    ) -> Result<Any, ChainClientError> {
+        self.revoke_authorization_msg(granter, grantee, "/cosmos.bank.v1beta1.MsgSend")
     }
 
     // Execute a transaction previously authorized by another account on its behalf
@@ -96,52 +341,81 @@ impl ChainClient {
         grantee: &AccountInfo,
         msgs: Vec<Any>,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any = self.execute_authorized_tx_msg(grantee, msgs)?;
+
+        self.sign_and_send_msgs(grantee, vec![msg_any], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgExec wrapping `msgs` for use with a [`crate::tx::TxBuilder`]
+    pub fn execute_authorized_tx_msg(
+        &self,
+        grantee: &AccountInfo,
+        msgs: Vec<Any>,
+    ) -> Result<Any, ChainClientError> {
         let msg = MsgExec {
             grantee: grantee.address(&self.config.account_prefix)?,
             msgs,
         };
-        let msg_any = Any {
+
+        Ok(Any {
             type_url: String::from("/cosmos.authz.v1beta1.MsgExec"),
             value: msg.encode_to_vec(),
-        };
-        let tx_metadata = match tx_metadata {
-            Some(tm) => tm,
-            None => self.get_basic_tx_metadata().await?,
-        };
-        let tx_body = tx::Body::new(vec![msg_any], &tx_metadata.memo, tx_metadata.timeout_height);
-
-        self.sign_and_send_msg(grantee, tx_body, tx_metadata).await
+        })
     }
+}
 
-    // Basic fee allowance
-    pub async fn perform_basic_allowance_fee_grant(
-        &mut self,
-        granter: &AccountInfo,
-        grantee: AccountId,
-        expiration: Option<Timestamp>,
-        // TODO: Standardize below Coin type to common cosmrs coin type once FeeGrants get looped in.
-        spend_limit: cosmos_sdk_proto::cosmos::base::v1beta1::Coin,
-        tx_metadata: TxMetadata,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
-        let allowance = BasicAllowance {
-            spend_limit: vec![spend_limit],
-            expiration,
-        };
-        let msg = MsgGrantAllowance {
-            granter: granter.address(&self.config.account_prefix)?,
-            grantee: grantee.to_string(),
-            allowance: Some(Any {
-                type_url: String::from("/cosmos.feegrant.v1beta1.BasicAllowance"),
-                value: allowance.encode_to_vec(),
-            }),
-        };
-        let msg_any = Any {
-            type_url: String::from("/cosmos.feegrant.v1beta1.MsgGrantAllowance"),
-            value: msg.encode_to_vec(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_authorizations() {
+        let authorizations = vec![
+            Authorization::generic("/cosmos.gov.v1beta1.MsgVote"),
+            Authorization::send(vec![Coin::new(1000000, "uatom")]),
+            Authorization::stake(
+                AuthorizationType::Delegate,
+                StakeValidators::AllowList(vec!["cosmosvaloper1a".to_string()]),
+                Some(Coin::new(5000000, "uatom")),
+            ),
+            Authorization::stake(
+                AuthorizationType::Redelegate,
+                StakeValidators::DenyList(vec!["cosmosvaloper1b".to_string()]),
+                None,
+            ),
+        ];
+
+        for authorization in authorizations {
+            assert_eq!(
+                Authorization::try_from(authorization.to_any()).unwrap(),
+                authorization
+            );
+        }
+
+        let unknown = Any {
+            type_url: "/ibc.applications.transfer.v1.TransferAuthorization".to_string(),
+            value: vec![1, 2, 3],
         };
-        let tx_body = tx::Body::new(vec![msg_any], &tx_metadata.memo, tx_metadata.timeout_height);
 
-        self.sign_and_send_msg(granter, tx_body, tx_metadata).await
+        assert_eq!(
+            Authorization::try_from(&unknown).unwrap(),
+            Authorization::Unknown(unknown)
+        );
+        assert_eq!(
+            Authorization::send(vec![]).msg_type_url().unwrap(),
+            "/cosmos.bank.v1beta1.MsgSend"
+        );
+        assert_eq!(
+            Authorization::stake(
+                AuthorizationType::Redelegate,
+                StakeValidators::AllowList(vec![]),
+                None
+            )
+            .msg_type_url()
+            .unwrap(),
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate"
+        );
     }
 }
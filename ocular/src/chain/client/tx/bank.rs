@@ -1,18 +1,16 @@
 use std::str::FromStr;
 
-use cosmrs::{
-    bank::{MsgMultiSend, MsgSend},
-    tx::{self, Msg},
-    AccountId,
-};
+use cosmrs::AccountId;
+use prost::Message;
 
 use crate::{
     account::AccountInfo,
+    cosmos_modules::bank::{MsgMultiSend, MsgSend},
     error::{ChainClientError, TxError},
-    tx::{Coin, MultiSendIo, TxMetadata},
+    tx::{Any, Coin, MultiSendIo, TxMetadata},
 };
 
-use super::{BroadcastCommitResponse, ChainClient};
+use super::{ChainClient, TxResponse};
 
 impl ChainClient {
     // TODO: Make this extensible to multisig and multicoin (or add new methods for that)
@@ -23,7 +21,20 @@ impl ChainClient {
         recipient: &str,
         amount: Coin,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.send_msg(sender, recipient, amount)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgSend for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn send_msg(
+        &self,
+        sender: &AccountInfo,
+        recipient: &str,
+        amount: Coin,
+    ) -> Result<Any, ChainClientError> {
         let recipient = match AccountId::from_str(recipient) {
             Ok(r) => r,
             Err(err) => {
@@ -44,21 +55,11 @@ impl ChainClient {
             .into());
         }
 
-        let msg = MsgSend {
-            from_address: sender.id(&self.config.account_prefix)?,
-            to_address: recipient,
-            amount: vec![cosmrs::Coin::try_from(amount)?],
-        };
-        let tx_metadata = match tx_metadata {
-            Some(tm) => tm,
-            None => self.get_basic_tx_metadata().await?,
-        };
-        let tx_body = match msg.to_any() {
-            Ok(msg) => tx::Body::new(vec![msg], &tx_metadata.memo, tx_metadata.timeout_height),
-            Err(err) => return Err(TxError::Serialization(err.to_string()).into()),
-        };
-
-        self.sign_and_send_msg(sender, tx_body, tx_metadata).await
+        Ok(msg_send(
+            sender.address(&self.config.account_prefix)?,
+            recipient.to_string(),
+            vec![amount],
+        ))
     }
 
     /// Send coins in a MIMO fashion. If any coin transfers are invalid the entire transaction will fail.
@@ -68,26 +69,100 @@ impl ChainClient {
         inputs: Vec<MultiSendIo>,
         outputs: Vec<MultiSendIo>,
         tx_metadata: Option<TxMetadata>,
-    ) -> Result<BroadcastCommitResponse, ChainClientError> {
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.multi_send_msg(inputs, outputs)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgMultiSend for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn multi_send_msg(
+        &self,
+        inputs: Vec<MultiSendIo>,
+        outputs: Vec<MultiSendIo>,
+    ) -> Result<Any, ChainClientError> {
         let msg = MsgMultiSend {
             inputs: inputs
                 .iter()
                 .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
+                .collect::<Result<_, TxError>>()?,
             outputs: outputs
                 .iter()
                 .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
+                .collect::<Result<_, TxError>>()?,
         };
-        let tx_metadata = match tx_metadata {
-            Some(tm) => tm,
-            None => self.get_basic_tx_metadata().await?,
-        };
-        let tx_body = match msg.to_any() {
-            Ok(msg) => tx::Body::new(vec![msg], &tx_metadata.memo, tx_metadata.timeout_height),
-            Err(err) => return Err(TxError::Serialization(err.to_string()).into()),
+
+        Ok(Any {
+            type_url: String::from("/cosmos.bank.v1beta1.MsgMultiSend"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
+
+/// Built from the protobuf types, whose string amounts hold any u128 amount
+fn msg_send(from_address: String, to_address: String, amount: Vec<Coin>) -> Any {
+    let msg = MsgSend {
+        from_address,
+        to_address,
+        amount: amount.into_iter().map(Into::into).collect(),
+    };
+
+    Any {
+        type_url: String::from("/cosmos.bank.v1beta1.MsgSend"),
+        value: msg.encode_to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cosmos_modules::bank::Input,
+        tx::{DecodedTx, Msg, SignerData, TxBuilder},
+    };
+
+    #[test]
+    fn sends_amounts_larger_than_u64() {
+        let sender = AccountInfo::new("");
+        let recipient = AccountInfo::new("").address("evmos").unwrap();
+        // 1 billion EVMOS
+        let amount = Coin::new(10u128.pow(27), "aevmos");
+        let fee = Coin::new(10u128.pow(20), "aevmos");
+
+        assert!(amount.amount > u64::MAX as u128 && fee.amount > u64::MAX as u128);
+
+        let signed = TxBuilder::new(SignerData::default())
+            .with_msg(msg_send(
+                sender.address("evmos").unwrap(),
+                recipient.clone(),
+                vec![amount.clone()],
+            ))
+            .with_tx_metadata(TxMetadata {
+                fee: vec![fee.clone()],
+                gas_limit: 200000,
+                ..Default::default()
+            })
+            .build(sender.public_key())
+            .unwrap()
+            .sign(&sender)
+            .unwrap();
+        let tx = DecodedTx::from_bytes(&signed.to_bytes()).unwrap();
+
+        match &tx.body.messages[0] {
+            Msg::Send(msg) => assert_eq!(msg.amount[0].amount, "1000000000000000000000000000"),
+            msg => panic!("unexpected msg {:?}", msg),
+        }
+        assert_eq!(tx.auth_info.fee.amount, vec![fee]);
+
+        let io = MultiSendIo {
+            address: recipient,
+            coins: vec![amount],
         };
 
-        self.sign_and_send_msg(sender, tx_body, tx_metadata).await
+        assert_eq!(
+            Input::try_from(&io).unwrap().coins[0].amount,
+            "1000000000000000000000000000"
+        );
     }
 }
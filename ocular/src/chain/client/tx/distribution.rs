@@ -0,0 +1,174 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::distribution::{
+        MsgFundCommunityPool, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+        MsgWithdrawValidatorCommission,
+    },
+    error::{ChainClientError, TxError},
+    tx::{Any, Coin, TxMetadata},
+};
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+impl ChainClient {
+    /// Withdraws the delegator's rewards from a single validator
+    pub async fn withdraw_delegator_reward(
+        &mut self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.withdraw_delegator_reward_msg(delegator, validator_address)?;
+
+        self.sign_and_send_msgs(delegator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgWithdrawDelegatorReward for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn withdraw_delegator_reward_msg(
+        &self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgWithdrawDelegatorReward {
+            delegator_address: delegator.address(&self.config.account_prefix)?,
+            validator_address: validator_address.to_string(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Withdraws the delegator's rewards from every validator it has delegated to in a single tx
+    pub async fn withdraw_all_rewards(
+        &mut self,
+        delegator: &AccountInfo,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msgs = self.withdraw_all_rewards_msgs(delegator).await?;
+
+        self.sign_and_send_msgs(delegator, msgs, tx_metadata).await
+    }
+
+    /// Creates a MsgWithdrawDelegatorReward for every validator the delegator has delegated to. Errors if there are
+    /// none.
+    pub async fn withdraw_all_rewards_msgs(
+        &mut self,
+        delegator: &AccountInfo,
+    ) -> Result<Vec<Any>, ChainClientError> {
+        let address = delegator.address(&self.config.account_prefix)?;
+        let validators = self.query_delegator_validators(&address).await?;
+
+        if validators.is_empty() {
+            return Err(TxError::Address(format!("{} has no delegations", address)).into());
+        }
+
+        validators
+            .iter()
+            .map(|v| self.withdraw_delegator_reward_msg(delegator, v))
+            .collect()
+    }
+
+    /// Withdraws the commission of the operator's validator
+    pub async fn withdraw_validator_commission(
+        &mut self,
+        operator: &AccountInfo,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.withdraw_validator_commission_msg(operator)?;
+
+        self.sign_and_send_msgs(operator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgWithdrawValidatorCommission for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn withdraw_validator_commission_msg(
+        &self,
+        operator: &AccountInfo,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgWithdrawValidatorCommission {
+            validator_address: operator.validator_address(&self.config.account_prefix)?,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Withdraws the operator's validator commission along with its rewards from all of its delegations, including
+    /// its self delegation, in a single tx
+    pub async fn withdraw_all_rewards_and_commission(
+        &mut self,
+        operator: &AccountInfo,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let mut msgs = self.withdraw_all_rewards_msgs(operator).await?;
+        msgs.push(self.withdraw_validator_commission_msg(operator)?);
+
+        self.sign_and_send_msgs(operator, msgs, tx_metadata).await
+    }
+
+    /// Sets the address the delegator's rewards are withdrawn to
+    pub async fn set_withdraw_address(
+        &mut self,
+        delegator: &AccountInfo,
+        withdraw_address: &str,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.set_withdraw_address_msg(delegator, withdraw_address)?;
+
+        self.sign_and_send_msgs(delegator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgSetWithdrawAddress for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn set_withdraw_address_msg(
+        &self,
+        delegator: &AccountInfo,
+        withdraw_address: &str,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgSetWithdrawAddress {
+            delegator_address: delegator.address(&self.config.account_prefix)?,
+            withdraw_address: withdraw_address.to_string(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.distribution.v1beta1.MsgSetWithdrawAddress"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Sends `amount` from the depositor to the community pool
+    pub async fn fund_community_pool(
+        &mut self,
+        depositor: &AccountInfo,
+        amount: Vec<Coin>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.fund_community_pool_msg(depositor, amount)?;
+
+        self.sign_and_send_msgs(depositor, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgFundCommunityPool for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn fund_community_pool_msg(
+        &self,
+        depositor: &AccountInfo,
+        amount: Vec<Coin>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgFundCommunityPool {
+            amount: amount.into_iter().map(Into::into).collect(),
+            depositor: depositor.address(&self.config.account_prefix)?,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.distribution.v1beta1.MsgFundCommunityPool"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
@@ -0,0 +1,391 @@
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::feegrant,
+    error::{ChainClientError, GrpcError, TxError},
+    tx::{Any, Coin, TxMetadata},
+    Timestamp,
+};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmrs::AccountId;
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+const BASIC_ALLOWANCE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+const PERIODIC_ALLOWANCE_URL: &str = "/cosmos.feegrant.v1beta1.PeriodicAllowance";
+const ALLOWED_MSG_ALLOWANCE_URL: &str = "/cosmos.feegrant.v1beta1.AllowedMsgAllowance";
+
+/// A fee allowance a granter gives a grantee. Empty spend limits mean no limit.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Allowance {
+    /// Allows the grantee to spend up to `spend_limit` on fees until `expiration`
+    Basic {
+        spend_limit: Vec<Coin>,
+        expiration: Option<Timestamp>,
+    },
+    /// A basic allowance that additionally limits spending to `period_spend_limit` per `period`
+    Periodic {
+        spend_limit: Vec<Coin>,
+        expiration: Option<Timestamp>,
+        period: Duration,
+        period_spend_limit: Vec<Coin>,
+        /// What's left to spend in the current period. Set by the chain; equal to `period_spend_limit` when granting.
+        period_can_spend: Vec<Coin>,
+        /// When the current period ends. Set by the chain; one period from now when granting.
+        period_reset: Option<Timestamp>,
+    },
+    /// Restricts another allowance to txs containing only the allowed msg types
+    AllowedMsg {
+        allowance: Box<Allowance>,
+        allowed_messages: Vec<String>,
+    },
+}
+
+impl Allowance {
+    pub fn basic(spend_limit: Vec<Coin>, expiration: Option<Timestamp>) -> Allowance {
+        Allowance::Basic {
+            spend_limit,
+            expiration,
+        }
+    }
+
+    /// Creates a periodic allowance whose first period starts now
+    pub fn periodic(
+        spend_limit: Vec<Coin>,
+        expiration: Option<Timestamp>,
+        period: Duration,
+        period_spend_limit: Vec<Coin>,
+    ) -> Allowance {
+        Allowance::Periodic {
+            spend_limit,
+            expiration,
+            period,
+            period_can_spend: period_spend_limit.clone(),
+            period_spend_limit,
+            period_reset: SystemTime::now().checked_add(period).map(Timestamp::from),
+        }
+    }
+
+    /// Wraps the allowance so it can only be used for txs containing the `allowed_messages` type URLs
+    pub fn with_allowed_messages(self, allowed_messages: Vec<String>) -> Allowance {
+        Allowance::AllowedMsg {
+            allowance: Box::new(self),
+            allowed_messages,
+        }
+    }
+
+    pub fn to_any(&self) -> Any {
+        match self {
+            Allowance::Basic {
+                spend_limit,
+                expiration,
+            } => Any {
+                type_url: BASIC_ALLOWANCE_URL.to_string(),
+                value: basic_allowance(spend_limit, expiration).encode_to_vec(),
+            },
+            Allowance::Periodic {
+                spend_limit,
+                expiration,
+                period,
+                period_spend_limit,
+                period_can_spend,
+                period_reset,
+            } => Any {
+                type_url: PERIODIC_ALLOWANCE_URL.to_string(),
+                value: feegrant::PeriodicAllowance {
+                    basic: Some(basic_allowance(spend_limit, expiration)),
+                    period: Some((*period).into()),
+                    period_spend_limit: proto_coins(period_spend_limit),
+                    period_can_spend: proto_coins(period_can_spend),
+                    period_reset: period_reset.clone(),
+                }
+                .encode_to_vec(),
+            },
+            Allowance::AllowedMsg {
+                allowance,
+                allowed_messages,
+            } => Any {
+                type_url: ALLOWED_MSG_ALLOWANCE_URL.to_string(),
+                value: feegrant::AllowedMsgAllowance {
+                    allowance: Some(allowance.to_any()),
+                    allowed_messages: allowed_messages.clone(),
+                }
+                .encode_to_vec(),
+            },
+        }
+    }
+}
+
+impl TryFrom<Any> for Allowance {
+    type Error = TxError;
+
+    fn try_from(any: Any) -> Result<Allowance, Self::Error> {
+        Allowance::try_from(&any)
+    }
+}
+
+impl TryFrom<&Any> for Allowance {
+    type Error = TxError;
+
+    fn try_from(any: &Any) -> Result<Allowance, Self::Error> {
+        match any.type_url.as_str() {
+            BASIC_ALLOWANCE_URL => {
+                let allowance = feegrant::BasicAllowance::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+
+                Ok(Allowance::Basic {
+                    spend_limit: coins(allowance.spend_limit)?,
+                    expiration: allowance.expiration,
+                })
+            }
+            PERIODIC_ALLOWANCE_URL => {
+                let allowance = feegrant::PeriodicAllowance::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+                let basic = allowance.basic.unwrap_or_default();
+                let period = allowance.period.unwrap_or_default();
+                let period =
+                    Duration::new(period.seconds.max(0) as u64, period.nanos.max(0) as u32);
+
+                Ok(Allowance::Periodic {
+                    spend_limit: coins(basic.spend_limit)?,
+                    expiration: basic.expiration,
+                    period,
+                    period_spend_limit: coins(allowance.period_spend_limit)?,
+                    period_can_spend: coins(allowance.period_can_spend)?,
+                    period_reset: allowance.period_reset,
+                })
+            }
+            ALLOWED_MSG_ALLOWANCE_URL => {
+                let allowance = feegrant::AllowedMsgAllowance::decode(&any.value[..])
+                    .map_err(|e| TxError::Serialization(e.to_string()))?;
+                let inner = allowance.allowance.ok_or_else(|| {
+                    TxError::TypeConversion("AllowedMsgAllowance has no allowance".to_string())
+                })?;
+
+                Ok(Allowance::AllowedMsg {
+                    allowance: Box::new(Allowance::try_from(inner)?),
+                    allowed_messages: allowance.allowed_messages,
+                })
+            }
+            type_url => Err(TxError::TypeConversion(format!(
+                "unsupported allowance type {}",
+                type_url
+            ))),
+        }
+    }
+}
+
+fn basic_allowance(
+    spend_limit: &[Coin],
+    expiration: &Option<Timestamp>,
+) -> feegrant::BasicAllowance {
+    feegrant::BasicAllowance {
+        spend_limit: proto_coins(spend_limit),
+        expiration: expiration.clone(),
+    }
+}
+
+fn proto_coins(coins: &[Coin]) -> Vec<ProtoCoin> {
+    coins.iter().cloned().map(ProtoCoin::from).collect()
+}
+
+fn coins(coins: Vec<ProtoCoin>) -> Result<Vec<Coin>, TxError> {
+    coins.into_iter().map(Coin::try_from).collect()
+}
+
+impl ChainClient {
+    /// Grants `grantee` an allowance to pay fees from the granter's account. Errors if the grantee already has an
+    /// allowance from the granter; see [`ChainClient::rotate_allowance`].
+    pub async fn grant_allowance(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        allowance: Allowance,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any = self.grant_allowance_msg(granter, grantee, &allowance)?;
+
+        self.sign_and_send_msgs(granter, vec![msg_any], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgGrantAllowance for use with a [`crate::tx::TxBuilder`]
+    pub fn grant_allowance_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        allowance: &Allowance,
+    ) -> Result<Any, ChainClientError> {
+        let msg = feegrant::MsgGrantAllowance {
+            granter: granter.address(&self.config.account_prefix)?,
+            grantee: grantee.to_string(),
+            allowance: Some(allowance.to_any()),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.feegrant.v1beta1.MsgGrantAllowance"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Revokes the granter's allowance to `grantee`
+    pub async fn revoke_allowance(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any = self.revoke_allowance_msg(granter, grantee)?;
+
+        self.sign_and_send_msgs(granter, vec![msg_any], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgRevokeAllowance for use with a [`crate::tx::TxBuilder`]
+    pub fn revoke_allowance_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+    ) -> Result<Any, ChainClientError> {
+        let msg = feegrant::MsgRevokeAllowance {
+            granter: granter.address(&self.config.account_prefix)?,
+            grantee: grantee.to_string(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.feegrant.v1beta1.MsgRevokeAllowance"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Replaces the granter's allowance to `grantee` with `allowance`. If an allowance already exists, it is revoked
+    /// in the same tx as the new one is granted, so the grantee is never left without an allowance.
+    pub async fn rotate_allowance(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        allowance: Allowance,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let granter_address = granter.address(&self.config.account_prefix)?;
+        let exists = match self
+            .query_allowance(&granter_address, &grantee.to_string())
+            .await
+        {
+            Ok(_) => true,
+            Err(err) if is_allowance_not_found(&err) => false,
+            Err(err) => return Err(err),
+        };
+        let mut msgs = Vec::new();
+
+        if exists {
+            msgs.push(self.revoke_allowance_msg(granter, grantee.clone())?);
+        }
+        msgs.push(self.grant_allowance_msg(granter, grantee, &allowance)?);
+
+        self.sign_and_send_msgs(granter, msgs, tx_metadata).await
+    }
+
+    // Basic fee allowance
+    pub async fn perform_basic_allowance_fee_grant(
+        &mut self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        expiration: Option<Timestamp>,
+        spend_limit: Vec<Coin>,
+        tx_metadata: TxMetadata,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg_any =
+            self.basic_allowance_fee_grant_msg(granter, grantee, expiration, spend_limit)?;
+
+        self.sign_and_send_msgs(granter, vec![msg_any], Some(tx_metadata))
+            .await
+    }
+
+    /// Creates a MsgGrantAllowance with a BasicAllowance for use with a [`crate::tx::TxBuilder`]
+    pub fn basic_allowance_fee_grant_msg(
+        &self,
+        granter: &AccountInfo,
+        grantee: AccountId,
+        expiration: Option<Timestamp>,
+        spend_limit: Vec<Coin>,
+    ) -> Result<Any, ChainClientError> {
+        self.grant_allowance_msg(granter, grantee, &Allowance::basic(spend_limit, expiration))
+    }
+}
+
+/// The feegrant module reports a missing allowance as a not found gRPC error, or an empty response
+fn is_allowance_not_found(err: &ChainClientError) -> bool {
+    match err {
+        ChainClientError::Grpc(GrpcError::Request(status)) => {
+            status.code() == tonic::Code::NotFound || status.message().contains("not found")
+        }
+        ChainClientError::ModuleQuery(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_missing_allowances() {
+        let not_found = ChainClientError::from(GrpcError::Request(tonic::Status::not_found(
+            "fee-grant not found: key not found",
+        )));
+        let unavailable = ChainClientError::from(GrpcError::Request(tonic::Status::unavailable(
+            "connection refused",
+        )));
+
+        assert!(is_allowance_not_found(&not_found));
+        assert!(!is_allowance_not_found(&unavailable));
+    }
+
+    #[test]
+    fn converts_allowances() {
+        let expiration = Some(Timestamp {
+            seconds: 1700000000,
+            nanos: 0,
+        });
+        let allowance = Allowance::periodic(
+            vec![Coin::new(1000000, "uatom")],
+            expiration.clone(),
+            Duration::from_secs(86400),
+            vec![Coin::new(10000, "uatom")],
+        )
+        .with_allowed_messages(vec!["/ibc.core.client.v1.MsgUpdateClient".to_string()]);
+        let any = allowance.to_any();
+
+        assert_eq!(any.type_url, ALLOWED_MSG_ALLOWANCE_URL);
+        assert_eq!(Allowance::try_from(&any).unwrap(), allowance);
+
+        match Allowance::try_from(any).unwrap() {
+            Allowance::AllowedMsg { allowance, .. } => match *allowance {
+                Allowance::Periodic {
+                    period,
+                    period_can_spend,
+                    period_reset,
+                    ..
+                } => {
+                    assert_eq!(period, Duration::from_secs(86400));
+                    assert_eq!(period_can_spend, vec![Coin::new(10000, "uatom")]);
+                    assert!(period_reset.is_some());
+                }
+                a => panic!("unexpected allowance {:?}", a),
+            },
+            a => panic!("unexpected allowance {:?}", a),
+        }
+
+        let basic = Allowance::basic(Vec::new(), expiration);
+
+        assert_eq!(Allowance::try_from(basic.to_any()).unwrap(), basic);
+        assert!(Allowance::try_from(Any {
+            type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
+            value: vec![],
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::{
+        distribution::CommunityPoolSpendProposal,
+        gov::{
+            MsgDeposit, MsgSubmitProposal, MsgVote, MsgVoteWeighted, TextProposal,
+            WeightedVoteOption,
+        },
+        params::ParameterChangeProposal,
+    },
+    error::{ChainClientError, TxError},
+    tx::{Any, Coin, Dec, TxMetadata},
+};
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+pub use crate::cosmos_modules::{gov::VoteOption, params::ParamChange};
+
+/// The content of a governance proposal
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProposalContent {
+    Text {
+        title: String,
+        description: String,
+    },
+    /// Spends `amount` from the community pool to `recipient` if the proposal passes
+    CommunityPoolSpend {
+        title: String,
+        description: String,
+        recipient: String,
+        amount: Vec<Coin>,
+    },
+    /// Changes module parameters if the proposal passes. Each change's value is the parameter's JSON encoding.
+    ParameterChange {
+        title: String,
+        description: String,
+        changes: Vec<ParamChange>,
+    },
+}
+
+impl ProposalContent {
+    pub fn text(title: &str, description: &str) -> ProposalContent {
+        ProposalContent::Text {
+            title: title.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    pub fn community_pool_spend(
+        title: &str,
+        description: &str,
+        recipient: &str,
+        amount: Vec<Coin>,
+    ) -> ProposalContent {
+        ProposalContent::CommunityPoolSpend {
+            title: title.to_string(),
+            description: description.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+        }
+    }
+
+    pub fn parameter_change(
+        title: &str,
+        description: &str,
+        changes: Vec<ParamChange>,
+    ) -> ProposalContent {
+        ProposalContent::ParameterChange {
+            title: title.to_string(),
+            description: description.to_string(),
+            changes,
+        }
+    }
+
+    pub fn to_any(&self) -> Any {
+        match self {
+            ProposalContent::Text { title, description } => Any {
+                type_url: String::from("/cosmos.gov.v1beta1.TextProposal"),
+                value: TextProposal {
+                    title: title.clone(),
+                    description: description.clone(),
+                }
+                .encode_to_vec(),
+            },
+            ProposalContent::CommunityPoolSpend {
+                title,
+                description,
+                recipient,
+                amount,
+            } => Any {
+                type_url: String::from("/cosmos.distribution.v1beta1.CommunityPoolSpendProposal"),
+                value: CommunityPoolSpendProposal {
+                    title: title.clone(),
+                    description: description.clone(),
+                    recipient: recipient.clone(),
+                    amount: amount.iter().cloned().map(Into::into).collect(),
+                }
+                .encode_to_vec(),
+            },
+            ProposalContent::ParameterChange {
+                title,
+                description,
+                changes,
+            } => Any {
+                type_url: String::from("/cosmos.params.v1beta1.ParameterChangeProposal"),
+                value: ParameterChangeProposal {
+                    title: title.clone(),
+                    description: description.clone(),
+                    changes: changes.clone(),
+                }
+                .encode_to_vec(),
+            },
+        }
+    }
+}
+
+// The gov msg builders take the signer's address rather than an [`AccountInfo`] so that an authz grantee can build
+// them on behalf of a granter whose key it doesn't hold, for use with [`ChainClient::execute_authorized_tx`].
+impl ChainClient {
+    /// Submits a governance proposal with an optional initial deposit
+    pub async fn submit_proposal(
+        &mut self,
+        proposer: &AccountInfo,
+        content: &ProposalContent,
+        initial_deposit: Vec<Coin>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let proposer_address = proposer.address(&self.config.account_prefix)?;
+        let msg = self.submit_proposal_msg(&proposer_address, content, initial_deposit)?;
+
+        self.sign_and_send_msgs(proposer, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgSubmitProposal for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn submit_proposal_msg(
+        &self,
+        proposer: &str,
+        content: &ProposalContent,
+        initial_deposit: Vec<Coin>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgSubmitProposal {
+            content: Some(content.to_any()),
+            initial_deposit: initial_deposit.into_iter().map(Into::into).collect(),
+            proposer: proposer.to_string(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.gov.v1beta1.MsgSubmitProposal"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Deposits `amount` to a proposal in its deposit period
+    pub async fn deposit(
+        &mut self,
+        depositor: &AccountInfo,
+        proposal_id: u64,
+        amount: Vec<Coin>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let depositor_address = depositor.address(&self.config.account_prefix)?;
+        let msg = self.deposit_msg(&depositor_address, proposal_id, amount)?;
+
+        self.sign_and_send_msgs(depositor, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgDeposit for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn deposit_msg(
+        &self,
+        depositor: &str,
+        proposal_id: u64,
+        amount: Vec<Coin>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgDeposit {
+            proposal_id,
+            depositor: depositor.to_string(),
+            amount: amount.into_iter().map(Into::into).collect(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.gov.v1beta1.MsgDeposit"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Votes on a proposal in its voting period
+    pub async fn vote(
+        &mut self,
+        voter: &AccountInfo,
+        proposal_id: u64,
+        option: VoteOption,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let voter_address = voter.address(&self.config.account_prefix)?;
+        let msg = self.vote_msg(&voter_address, proposal_id, option)?;
+
+        self.sign_and_send_msgs(voter, vec![msg], tx_metadata).await
+    }
+
+    /// Creates a MsgVote for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn vote_msg(
+        &self,
+        voter: &str,
+        proposal_id: u64,
+        option: VoteOption,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgVote {
+            proposal_id,
+            voter: voter.to_string(),
+            option: option as i32,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.gov.v1beta1.MsgVote"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Splits a vote across several options. The weights must add up to exactly 1.
+    pub async fn vote_weighted(
+        &mut self,
+        voter: &AccountInfo,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Dec)>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let voter_address = voter.address(&self.config.account_prefix)?;
+        let msg = self.vote_weighted_msg(&voter_address, proposal_id, options)?;
+
+        self.sign_and_send_msgs(voter, vec![msg], tx_metadata).await
+    }
+
+    /// Creates a MsgVoteWeighted for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn vote_weighted_msg(
+        &self,
+        voter: &str,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Dec)>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgVoteWeighted {
+            proposal_id,
+            voter: voter.to_string(),
+            options: weighted_vote_options(options)?,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.gov.v1beta1.MsgVoteWeighted"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
+
+/// Validates the options the same way the SDK does: each option appears once and the weights add up to 1
+fn weighted_vote_options(
+    options: Vec<(VoteOption, Dec)>,
+) -> Result<Vec<WeightedVoteOption>, TxError> {
+    let one = Dec::from_integer(1);
+    let mut total = Dec::default();
+
+    for (i, (option, weight)) in options.iter().enumerate() {
+        if *option == VoteOption::Unspecified || *weight == Dec::default() {
+            return Err(TxError::TypeConversion(format!(
+                "invalid weighted vote option {:?} with weight {}",
+                option, weight
+            )));
+        }
+        if options[..i].iter().any(|(o, _)| o == option) {
+            return Err(TxError::TypeConversion(format!(
+                "duplicate weighted vote option {:?}",
+                option
+            )));
+        }
+
+        total = total.checked_add(*weight).unwrap_or(Dec::MAX);
+    }
+
+    if total != one {
+        return Err(TxError::TypeConversion(format!(
+            "weighted vote options must add up to 1, got {}",
+            total
+        )));
+    }
+
+    Ok(options
+        .into_iter()
+        .map(|(option, weight)| WeightedVoteOption {
+            option: option as i32,
+            weight: weight.to_atomics_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_proposal_contents() {
+        let spend = ProposalContent::community_pool_spend(
+            "Fund relayers",
+            "Pays relayer operators",
+            "cosmos1recipient",
+            vec![Coin::new(1000000, "uatom")],
+        );
+        let any = spend.to_any();
+        let decoded = CommunityPoolSpendProposal::decode(&any.value[..]).unwrap();
+
+        assert_eq!(
+            any.type_url,
+            "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal"
+        );
+        assert_eq!(decoded.recipient, "cosmos1recipient");
+        assert_eq!(decoded.amount[0].amount, "1000000");
+
+        let change = ProposalContent::parameter_change(
+            "Raise max validators",
+            "",
+            vec![ParamChange {
+                subspace: "staking".to_string(),
+                key: "MaxValidators".to_string(),
+                value: "150".to_string(),
+            }],
+        );
+        let decoded = ParameterChangeProposal::decode(&change.to_any().value[..]).unwrap();
+
+        assert_eq!(decoded.changes[0].key, "MaxValidators");
+    }
+
+    #[test]
+    fn validates_weighted_vote_options() {
+        let half: Dec = "0.5".parse().unwrap();
+        let options =
+            weighted_vote_options(vec![(VoteOption::Yes, half), (VoteOption::Abstain, half)])
+                .unwrap();
+
+        assert_eq!(options[0].option, VoteOption::Yes as i32);
+        assert_eq!(options[1].weight, "500000000000000000");
+        assert!(weighted_vote_options(vec![(VoteOption::Yes, half)]).is_err());
+        assert!(
+            weighted_vote_options(vec![(VoteOption::Yes, half), (VoteOption::Yes, half)]).is_err()
+        );
+        assert!(weighted_vote_options(vec![
+            (VoteOption::Unspecified, half),
+            (VoteOption::No, half)
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    account::{AccountId, AccountInfo},
+    error::{ChainClientError, TxError},
+    registry::TransferChannel,
+    tx::{Any, Coin, TxMetadata},
+    Timestamp,
+};
+#[cfg(feature = "registry-cache")]
+use crate::{error::ChainRegistryError, registry::RegistryCache};
+use cosmos_sdk_proto::ibc::{applications::transfer::v1::MsgTransfer, core::client::v1::Height};
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+/// How long after being built an IBC transfer times out by default
+pub const DEFAULT_IBC_TRANSFER_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// When an IBC transfer that hasn't been received on the destination chain times out and is refunded
+#[derive(Clone, Debug, PartialEq)]
+pub enum IbcTimeout {
+    /// Times out this long after the msg is built
+    After(Duration),
+    /// Times out at a block height of the destination chain
+    Height {
+        revision_number: u64,
+        revision_height: u64,
+    },
+    /// Times out at a time according to the destination chain's block time
+    Timestamp(Timestamp),
+}
+
+impl Default for IbcTimeout {
+    fn default() -> Self {
+        IbcTimeout::After(DEFAULT_IBC_TRANSFER_TIMEOUT)
+    }
+}
+
+impl IbcTimeout {
+    /// The msg's timeout height and timeout timestamp in nanoseconds. Zero values are disabled.
+    fn resolve(&self) -> Result<(Option<Height>, u64), TxError> {
+        match self {
+            IbcTimeout::After(duration) => {
+                let timeout = SystemTime::now()
+                    .checked_add(*duration)
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .ok_or_else(|| {
+                        TxError::TypeConversion(format!("invalid IBC timeout {:?}", duration))
+                    })?;
+
+                Ok((None, timeout.as_nanos() as u64))
+            }
+            IbcTimeout::Height {
+                revision_number,
+                revision_height,
+            } => Ok((
+                Some(Height {
+                    revision_number: *revision_number,
+                    revision_height: *revision_height,
+                }),
+                0,
+            )),
+            IbcTimeout::Timestamp(timestamp) => {
+                let nanos = u64::try_from(timestamp.seconds)
+                    .ok()
+                    .and_then(|s| s.checked_mul(1_000_000_000))
+                    .and_then(|n| n.checked_add(timestamp.nanos as u64))
+                    .ok_or_else(|| {
+                        TxError::TypeConversion(format!("invalid IBC timeout {:?}", timestamp))
+                    })?;
+
+                Ok((None, nanos))
+            }
+        }
+    }
+}
+
+impl ChainClient {
+    /// Sends `coin` to `recipient` on `destination_chain` over the preferred live transfer channel between the two
+    /// chains listed in the chain registry. Tokens sent to an address of the wrong chain are lost, so the
+    /// recipient's bech32 prefix must match the destination chain's, which is taken from `paths` if it has the
+    /// chain's info and from the chain registry otherwise.
+    #[cfg(feature = "registry-cache")]
+    pub async fn ibc_transfer(
+        &mut self,
+        sender: &AccountInfo,
+        paths: &RegistryCache,
+        destination_chain: &str,
+        recipient: &str,
+        coin: Coin,
+        timeout: IbcTimeout,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let channel = paths
+            .get_transfer_channel(&self.config.chain_name, destination_chain)
+            .await?
+            .ok_or_else(|| {
+                ChainRegistryError::UnsupportedChain(format!(
+                    "no transfer channel between {} and {} in the registry",
+                    self.config.chain_name, destination_chain
+                ))
+            })?;
+
+        let info = paths.get_chain(destination_chain).await?.ok_or_else(|| {
+            ChainRegistryError::UnsupportedChain(format!(
+                "no chain info for {} in the registry cache or the chain registry",
+                destination_chain
+            ))
+        })?;
+
+        self.ibc_transfer_over_channel(
+            sender,
+            &channel,
+            recipient,
+            Some(&info.bech32_prefix),
+            coin,
+            timeout,
+            tx_metadata,
+        )
+        .await
+    }
+
+    /// Sends `coin` to `recipient` over an explicitly chosen channel, which also works for chains that aren't in the
+    /// chain registry. If `recipient_prefix` is set, the recipient's address must have that bech32 prefix.
+    pub async fn ibc_transfer_over_channel(
+        &mut self,
+        sender: &AccountInfo,
+        channel: &TransferChannel,
+        recipient: &str,
+        recipient_prefix: Option<&str>,
+        coin: Coin,
+        timeout: IbcTimeout,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        if let Some(prefix) = recipient_prefix {
+            check_recipient(recipient, prefix)?;
+        }
+
+        let msg = self.ibc_transfer_msg(sender, channel, recipient, coin, timeout)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgTransfer for use with a [`crate::tx::TxBuilder`] or in an authz exec. Unlike
+    /// [`ChainClient::ibc_transfer`], the recipient's address is not checked against the destination chain.
+    pub fn ibc_transfer_msg(
+        &self,
+        sender: &AccountInfo,
+        channel: &TransferChannel,
+        recipient: &str,
+        coin: Coin,
+        timeout: IbcTimeout,
+    ) -> Result<Any, ChainClientError> {
+        let (timeout_height, timeout_timestamp) = timeout.resolve()?;
+        let msg = MsgTransfer {
+            source_port: channel.port_id.clone(),
+            source_channel: channel.channel_id.clone(),
+            token: Some(coin.into()),
+            sender: sender.address(&self.config.account_prefix)?,
+            receiver: recipient.to_string(),
+            timeout_height,
+            timeout_timestamp,
+        };
+
+        Ok(Any {
+            type_url: String::from("/ibc.applications.transfer.v1.MsgTransfer"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
+
+/// Tokens sent to an address of the wrong chain are lost, so the recipient's bech32 prefix must match the
+/// destination chain's
+fn check_recipient(recipient: &str, prefix: &str) -> Result<(), TxError> {
+    let recipient_id = AccountId::from_str(recipient)
+        .map_err(|e| TxError::Address(format!("invalid recipient {}: {}", recipient, e)))?;
+
+    if recipient_id.prefix() != prefix {
+        return Err(TxError::Address(format!(
+            "recipient {} doesn't have the destination chain's prefix {}",
+            recipient, prefix
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_timeouts() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+        let (height, timestamp) = IbcTimeout::default().resolve().unwrap();
+
+        assert_eq!(height, None);
+        assert!(timestamp >= now + DEFAULT_IBC_TRANSFER_TIMEOUT.as_nanos() as u64);
+
+        let (height, timestamp) = IbcTimeout::Height {
+            revision_number: 4,
+            revision_height: 12000000,
+        }
+        .resolve()
+        .unwrap();
+
+        assert_eq!(height.unwrap().revision_height, 12000000);
+        assert_eq!(timestamp, 0);
+        assert_eq!(
+            IbcTimeout::Timestamp(Timestamp {
+                seconds: 1660000000,
+                nanos: 5,
+            })
+            .resolve()
+            .unwrap(),
+            (None, 1660000000000000005)
+        );
+        assert!(IbcTimeout::Timestamp(Timestamp {
+            seconds: -1,
+            nanos: 0,
+        })
+        .resolve()
+        .is_err());
+    }
+
+    #[test]
+    fn checks_recipient_prefix() {
+        let recipient = AccountInfo::new("").address("osmo").unwrap();
+
+        assert!(check_recipient(&recipient, "osmo").is_ok());
+        assert!(matches!(
+            check_recipient(&recipient, "cosmos"),
+            Err(TxError::Address(_))
+        ));
+        assert!(check_recipient("osmo1invalid", "osmo").is_err());
+    }
+}
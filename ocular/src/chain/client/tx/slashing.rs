@@ -0,0 +1,35 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::slashing::MsgUnjail,
+    error::ChainClientError,
+    tx::{Any, TxMetadata},
+};
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+impl ChainClient {
+    /// Unjails the operator's validator once its jail period has passed
+    pub async fn unjail(
+        &mut self,
+        operator: &AccountInfo,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.unjail_msg(operator)?;
+
+        self.sign_and_send_msgs(operator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgUnjail for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn unjail_msg(&self, operator: &AccountInfo) -> Result<Any, ChainClientError> {
+        let msg = MsgUnjail {
+            validator_addr: operator.validator_address(&self.config.account_prefix)?,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.slashing.v1beta1.MsgUnjail"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::staking::{
+        self, Description, MsgBeginRedelegate, MsgCreateValidator, MsgDelegate, MsgEditValidator,
+        MsgUndelegate,
+    },
+    error::{ChainClientError, TxError},
+    tx::{Any, Coin, Dec, TxMetadata},
+};
+use cosmos_sdk_proto::cosmos::crypto::ed25519;
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+/// Description fields set to this value are left unchanged by [`ChainClient::edit_validator`]
+pub const DO_NOT_MODIFY: &str = "[do-not-modify]";
+
+/// Commission rates of a new validator. `max_rate` and `max_change_rate` (per day) can't be changed later.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CommissionRates {
+    pub rate: Dec,
+    pub max_rate: Dec,
+    pub max_change_rate: Dec,
+}
+
+impl From<CommissionRates> for staking::CommissionRates {
+    fn from(rates: CommissionRates) -> Self {
+        staking::CommissionRates {
+            rate: rates.rate.to_atomics_string(),
+            max_rate: rates.max_rate.to_atomics_string(),
+            max_change_rate: rates.max_change_rate.to_atomics_string(),
+        }
+    }
+}
+
+impl ChainClient {
+    /// Delegates `amount` from the delegator to the validator with operator address `validator_address`
+    pub async fn delegate(
+        &mut self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+        amount: Coin,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.delegate_msg(delegator, validator_address, amount)?;
+
+        self.sign_and_send_msgs(delegator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgDelegate for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn delegate_msg(
+        &self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+        amount: Coin,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgDelegate {
+            delegator_address: delegator.address(&self.config.account_prefix)?,
+            validator_address: validator_address.to_string(),
+            amount: Some(amount.into()),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.staking.v1beta1.MsgDelegate"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Undelegates `amount` from a validator. The tokens are returned after the chain's unbonding period.
+    pub async fn undelegate(
+        &mut self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+        amount: Coin,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.undelegate_msg(delegator, validator_address, amount)?;
+
+        self.sign_and_send_msgs(delegator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgUndelegate for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn undelegate_msg(
+        &self,
+        delegator: &AccountInfo,
+        validator_address: &str,
+        amount: Coin,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgUndelegate {
+            delegator_address: delegator.address(&self.config.account_prefix)?,
+            validator_address: validator_address.to_string(),
+            amount: Some(amount.into()),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.staking.v1beta1.MsgUndelegate"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Moves `amount` of a delegation from one validator to another without unbonding
+    pub async fn begin_redelegate(
+        &mut self,
+        delegator: &AccountInfo,
+        validator_src_address: &str,
+        validator_dst_address: &str,
+        amount: Coin,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.begin_redelegate_msg(
+            delegator,
+            validator_src_address,
+            validator_dst_address,
+            amount,
+        )?;
+
+        self.sign_and_send_msgs(delegator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgBeginRedelegate for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn begin_redelegate_msg(
+        &self,
+        delegator: &AccountInfo,
+        validator_src_address: &str,
+        validator_dst_address: &str,
+        amount: Coin,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgBeginRedelegate {
+            delegator_address: delegator.address(&self.config.account_prefix)?,
+            validator_src_address: validator_src_address.to_string(),
+            validator_dst_address: validator_dst_address.to_string(),
+            amount: Some(amount.into()),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.staking.v1beta1.MsgBeginRedelegate"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Creates a validator operated by `operator`, with `value` as its initial self delegation.
+    /// `consensus_public_key` is the validator node's 32 byte ed25519 public key, found in its
+    /// `priv_validator_key.json`.
+    pub async fn create_validator(
+        &mut self,
+        operator: &AccountInfo,
+        consensus_public_key: &[u8],
+        description: Description,
+        commission: CommissionRates,
+        min_self_delegation: u128,
+        value: Coin,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.create_validator_msg(
+            operator,
+            consensus_public_key,
+            description,
+            commission,
+            min_self_delegation,
+            value,
+        )?;
+
+        self.sign_and_send_msgs(operator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgCreateValidator for use with a [`crate::tx::TxBuilder`]
+    pub fn create_validator_msg(
+        &self,
+        operator: &AccountInfo,
+        consensus_public_key: &[u8],
+        description: Description,
+        commission: CommissionRates,
+        min_self_delegation: u128,
+        value: Coin,
+    ) -> Result<Any, ChainClientError> {
+        if consensus_public_key.len() != 32 {
+            return Err(TxError::TypeConversion(format!(
+                "ed25519 public keys are 32 bytes, got {}",
+                consensus_public_key.len()
+            ))
+            .into());
+        }
+
+        let msg = MsgCreateValidator {
+            description: Some(description),
+            commission: Some(commission.into()),
+            min_self_delegation: min_self_delegation.to_string(),
+            delegator_address: operator.address(&self.config.account_prefix)?,
+            validator_address: operator.validator_address(&self.config.account_prefix)?,
+            pubkey: Some(Any {
+                type_url: String::from("/cosmos.crypto.ed25519.PubKey"),
+                value: ed25519::PubKey {
+                    key: consensus_public_key.to_vec(),
+                }
+                .encode_to_vec(),
+            }),
+            value: Some(value.into()),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.staking.v1beta1.MsgCreateValidator"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Edits the operator's validator. Description fields set to [`DO_NOT_MODIFY`] are left unchanged, as is
+    /// everything else that is `None`. The commission rate can only be changed once per day.
+    pub async fn edit_validator(
+        &mut self,
+        operator: &AccountInfo,
+        description: Option<Description>,
+        commission_rate: Option<Dec>,
+        min_self_delegation: Option<u128>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg =
+            self.edit_validator_msg(operator, description, commission_rate, min_self_delegation)?;
+
+        self.sign_and_send_msgs(operator, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgEditValidator for use with a [`crate::tx::TxBuilder`]
+    pub fn edit_validator_msg(
+        &self,
+        operator: &AccountInfo,
+        description: Option<Description>,
+        commission_rate: Option<Dec>,
+        min_self_delegation: Option<u128>,
+    ) -> Result<Any, ChainClientError> {
+        let description = description.unwrap_or_else(|| Description {
+            moniker: DO_NOT_MODIFY.to_string(),
+            identity: DO_NOT_MODIFY.to_string(),
+            website: DO_NOT_MODIFY.to_string(),
+            security_contact: DO_NOT_MODIFY.to_string(),
+            details: DO_NOT_MODIFY.to_string(),
+        });
+        // empty strings leave the values unchanged
+        let msg = MsgEditValidator {
+            description: Some(description),
+            validator_address: operator.validator_address(&self.config.account_prefix)?,
+            commission_rate: commission_rate
+                .map(|r| r.to_atomics_string())
+                .unwrap_or_default(),
+            min_self_delegation: min_self_delegation
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.staking.v1beta1.MsgEditValidator"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_commission_rates() {
+        let rates = staking::CommissionRates::from(CommissionRates {
+            rate: "0.05".parse().unwrap(),
+            max_rate: "0.2".parse().unwrap(),
+            max_change_rate: "0.01".parse().unwrap(),
+        });
+
+        assert_eq!(rates.rate, "50000000000000000");
+        assert_eq!(rates.max_rate, "200000000000000000");
+        assert_eq!(rates.max_change_rate, "10000000000000000");
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::vesting::MsgCreateVestingAccount,
+    error::ChainClientError,
+    tx::{Any, Coin, TxMetadata},
+};
+use prost::Message;
+
+use super::{ChainClient, TxResponse};
+
+impl ChainClient {
+    /// Creates a vesting account at `to_address` funded with `amount`. Continuous vesting accounts vest linearly
+    /// from the block time the tx is included in until `end_time`, a unix timestamp in seconds; delayed vesting
+    /// accounts vest all at once at `end_time`.
+    pub async fn create_vesting_account(
+        &mut self,
+        sender: &AccountInfo,
+        to_address: &str,
+        amount: Vec<Coin>,
+        end_time: i64,
+        delayed: bool,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.create_vesting_account_msg(sender, to_address, amount, end_time, delayed)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgCreateVestingAccount for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn create_vesting_account_msg(
+        &self,
+        sender: &AccountInfo,
+        to_address: &str,
+        amount: Vec<Coin>,
+        end_time: i64,
+        delayed: bool,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgCreateVestingAccount {
+            from_address: sender.address(&self.config.account_prefix)?,
+            to_address: to_address.to_string(),
+            amount: amount.into_iter().map(Into::into).collect(),
+            end_time,
+            delayed,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmos.vesting.v1beta1.MsgCreateVestingAccount"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
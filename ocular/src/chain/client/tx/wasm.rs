@@ -0,0 +1,208 @@
+use crate::{
+    account::AccountInfo,
+    cosmos_modules::wasm::{
+        MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+    },
+    error::{ChainClientError, TxError},
+    tx::{Any, Coin, TxMetadata},
+};
+use prost::Message;
+use serde::Serialize;
+
+use super::{ChainClient, TxResponse};
+
+impl TxResponse {
+    /// The code id assigned by a committed MsgStoreCode
+    pub fn code_id(&self) -> Option<u64> {
+        self.find_event_attribute("store_code", "code_id")?
+            .parse()
+            .ok()
+    }
+
+    /// The address of the contract created by a committed MsgInstantiateContract
+    pub fn contract_address(&self) -> Option<String> {
+        self.find_event_attribute("instantiate", "_contract_address")
+    }
+}
+
+impl ChainClient {
+    /// Uploads contract code. The assigned code id can be read with [`TxResponse::code_id`].
+    pub async fn store_code(
+        &mut self,
+        sender: &AccountInfo,
+        wasm_byte_code: Vec<u8>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.store_code_msg(sender, wasm_byte_code)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgStoreCode for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn store_code_msg(
+        &self,
+        sender: &AccountInfo,
+        wasm_byte_code: Vec<u8>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgStoreCode {
+            sender: sender.address(&self.config.account_prefix)?,
+            wasm_byte_code,
+            instantiate_permission: None,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmwasm.wasm.v1.MsgStoreCode"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Instantiates a contract from stored code with `msg` serialized as its JSON instantiate message. The new
+    /// contract's address can be read with [`TxResponse::contract_address`].
+    pub async fn instantiate_contract<M: Serialize>(
+        &mut self,
+        sender: &AccountInfo,
+        code_id: u64,
+        label: &str,
+        msg: &M,
+        funds: Vec<Coin>,
+        admin: Option<&str>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.instantiate_contract_msg(sender, code_id, label, msg, funds, admin)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgInstantiateContract for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn instantiate_contract_msg<M: Serialize>(
+        &self,
+        sender: &AccountInfo,
+        code_id: u64,
+        label: &str,
+        msg: &M,
+        funds: Vec<Coin>,
+        admin: Option<&str>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgInstantiateContract {
+            sender: sender.address(&self.config.account_prefix)?,
+            admin: admin.unwrap_or_default().to_string(),
+            code_id,
+            label: label.to_string(),
+            msg: contract_msg(msg)?,
+            funds: funds.into_iter().map(Into::into).collect(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmwasm.wasm.v1.MsgInstantiateContract"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Executes `contract` with `msg` serialized as its JSON execute message, sending it `funds`
+    pub async fn execute_contract<M: Serialize>(
+        &mut self,
+        sender: &AccountInfo,
+        contract: &str,
+        msg: &M,
+        funds: Vec<Coin>,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.execute_contract_msg(sender, contract, msg, funds)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgExecuteContract for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn execute_contract_msg<M: Serialize>(
+        &self,
+        sender: &AccountInfo,
+        contract: &str,
+        msg: &M,
+        funds: Vec<Coin>,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgExecuteContract {
+            sender: sender.address(&self.config.account_prefix)?,
+            contract: contract.to_string(),
+            msg: contract_msg(msg)?,
+            funds: funds.into_iter().map(Into::into).collect(),
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmwasm.wasm.v1.MsgExecuteContract"),
+            value: msg.encode_to_vec(),
+        })
+    }
+
+    /// Migrates `contract` to the code `code_id` with `msg` serialized as its JSON migrate message. The sender must
+    /// be the contract's admin.
+    pub async fn migrate_contract<M: Serialize>(
+        &mut self,
+        sender: &AccountInfo,
+        contract: &str,
+        code_id: u64,
+        msg: &M,
+        tx_metadata: Option<TxMetadata>,
+    ) -> Result<TxResponse, ChainClientError> {
+        let msg = self.migrate_contract_msg(sender, contract, code_id, msg)?;
+
+        self.sign_and_send_msgs(sender, vec![msg], tx_metadata)
+            .await
+    }
+
+    /// Creates a MsgMigrateContract for use with a [`crate::tx::TxBuilder`] or in an authz exec.
+    pub fn migrate_contract_msg<M: Serialize>(
+        &self,
+        sender: &AccountInfo,
+        contract: &str,
+        code_id: u64,
+        msg: &M,
+    ) -> Result<Any, ChainClientError> {
+        let msg = MsgMigrateContract {
+            sender: sender.address(&self.config.account_prefix)?,
+            contract: contract.to_string(),
+            code_id,
+            msg: contract_msg(msg)?,
+        };
+
+        Ok(Any {
+            type_url: String::from("/cosmwasm.wasm.v1.MsgMigrateContract"),
+            value: msg.encode_to_vec(),
+        })
+    }
+}
+
+/// Contracts receive their messages as JSON bytes
+fn contract_msg<M: Serialize>(msg: &M) -> Result<Vec<u8>, TxError> {
+    serde_json::to_vec(msg).map_err(|e| TxError::Serialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_contract_msgs() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "snake_case")]
+        enum ExecuteMsg {
+            Transfer { recipient: String, amount: String },
+        }
+
+        assert_eq!(
+            contract_msg(&ExecuteMsg::Transfer {
+                recipient: "juno1recipient".to_string(),
+                amount: "100".to_string(),
+            })
+            .unwrap(),
+            br#"{"transfer":{"recipient":"juno1recipient","amount":"100"}}"#.to_vec()
+        );
+        assert_eq!(
+            contract_msg(&json!({ "increment": {} })).unwrap(),
+            br#"{"increment":{}}"#.to_vec()
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! A local, append-only log of broadcast transactions stored as JSON lines.
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::PublicKey,
+    error::TxError,
+    tx::{Any, Coin, MultisigAccount},
+};
+
+use super::tx::{TxResponse, TxResult};
+
+pub const DEFAULT_TX_LOG_DIR: &str = ".ocular/logs/txs";
+pub const TX_LOG_FILE_NAME: &str = "txs.jsonl";
+/// Unix permissions for dir
+const TX_LOG_DIR_PERMISSIONS: u32 = 0o700;
+/// Unix permissions for the log file
+const TX_LOG_FILE_PERMISSIONS: u32 = 0o600;
+
+/// Outcome of a logged tx at the time it was broadcast
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    /// The tx was committed and DeliverTx succeeded
+    Success,
+    /// The tx failed CheckTx or DeliverTx
+    Failed,
+    /// The tx was broadcast without waiting for it to be committed
+    Pending,
+}
+
+/// A single line of the tx log
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TxLogRecord {
+    /// Unix timestamp in seconds of when the tx was broadcast
+    pub timestamp: u64,
+    pub chain_id: String,
+    /// Address of the first signer. `None` if it could not be derived from the signer's public key.
+    pub signer: Option<String>,
+    pub msg_type_urls: Vec<String>,
+    pub hash: String,
+    pub height: Option<u64>,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub fee: Vec<Coin>,
+    pub status: TxStatus,
+    pub code: u32,
+    pub codespace: String,
+}
+
+impl TxLogRecord {
+    /// Creates a record from the broadcast tx bytes and the node's response. `account_prefix` is used to derive
+    /// the signer's address.
+    pub fn new(
+        chain_id: &str,
+        account_prefix: &str,
+        tx_bytes: &[u8],
+        response: &TxResponse,
+    ) -> Result<TxLogRecord, TxError> {
+        let tx_raw = TxRaw::decode(tx_bytes).map_err(|e| TxError::Serialization(e.to_string()))?;
+        let body = TxBody::decode(&tx_raw.body_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let auth_info = AuthInfo::decode(&tx_raw.auth_info_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let signer = auth_info
+            .signer_infos
+            .first()
+            .and_then(|s| s.public_key.clone())
+            .and_then(|k| signer_address(k, account_prefix));
+        let fee = auth_info
+            .fee
+            .map(|f| f.amount)
+            .unwrap_or_default()
+            .into_iter()
+            .map(Coin::try_from)
+            .collect::<Result<Vec<Coin>, _>>()?;
+
+        // DeliverTx supersedes CheckTx when both are present
+        let result = response.deliver_tx.as_ref().or(response.check_tx.as_ref());
+        let status = if [&response.check_tx, &response.deliver_tx]
+            .into_iter()
+            .flatten()
+            .any(TxResult::is_err)
+        {
+            TxStatus::Failed
+        } else if response.deliver_tx.is_some() {
+            TxStatus::Success
+        } else {
+            TxStatus::Pending
+        };
+
+        Ok(TxLogRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            chain_id: chain_id.to_string(),
+            signer,
+            msg_type_urls: body.messages.into_iter().map(|m| m.type_url).collect(),
+            hash: response.hash.to_string(),
+            height: response.height,
+            gas_wanted: result.map_or(0, |r| r.gas_wanted),
+            gas_used: result.map_or(0, |r| r.gas_used),
+            fee,
+            status,
+            code: result.map_or(0, |r| r.code),
+            codespace: result.map(|r| r.codespace.clone()).unwrap_or_default(),
+        })
+    }
+}
+
+fn signer_address(public_key: Any, account_prefix: &str) -> Option<String> {
+    match PublicKey::try_from(public_key.clone()) {
+        Ok(key) => key.account_id(account_prefix).ok().map(|id| id.to_string()),
+        Err(_) => MultisigAccount::try_from(public_key)
+            .ok()?
+            .address(account_prefix)
+            .ok(),
+    }
+}
+
+/// Filter for [`TxLog::list`]. All fields are optional and unset fields match every record.
+#[derive(Clone, Debug, Default)]
+pub struct TxLogFilter {
+    pub chain_id: Option<String>,
+    pub signer: Option<String>,
+    /// Inclusive lower bound as a unix timestamp in seconds
+    pub from: Option<u64>,
+    /// Inclusive upper bound as a unix timestamp in seconds
+    pub to: Option<u64>,
+    pub status: Option<TxStatus>,
+}
+
+impl TxLogFilter {
+    pub fn with_chain_id(mut self, chain_id: &str) -> TxLogFilter {
+        self.chain_id = Some(chain_id.to_string());
+        self
+    }
+
+    pub fn with_signer(mut self, signer: &str) -> TxLogFilter {
+        self.signer = Some(signer.to_string());
+        self
+    }
+
+    pub fn with_time_range(mut self, from: Option<u64>, to: Option<u64>) -> TxLogFilter {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    pub fn with_status(mut self, status: TxStatus) -> TxLogFilter {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn matches(&self, record: &TxLogRecord) -> bool {
+        self.chain_id
+            .as_ref()
+            .map_or(true, |c| *c == record.chain_id)
+            && self
+                .signer
+                .as_ref()
+                .map_or(true, |s| record.signer.as_ref() == Some(s))
+            && self.from.map_or(true, |f| record.timestamp >= f)
+            && self.to.map_or(true, |t| record.timestamp <= t)
+            && self.status.map_or(true, |s| s == record.status)
+    }
+}
+
+/// Stores [`TxLogRecord`]s in a JSON lines file. A [`crate::chain::client::ChainClient`] appends a record for
+/// every tx it broadcasts unless its `tx_log` is set to `None`.
+#[derive(Clone, Debug)]
+pub struct TxLog {
+    dir: PathBuf,
+}
+
+impl TxLog {
+    pub fn new(dir: impl Into<PathBuf>) -> TxLog {
+        TxLog { dir: dir.into() }
+    }
+
+    /// Logs to ~/.ocular/logs/txs. Returns `None` if the home directory can't be determined.
+    pub fn try_default() -> Option<TxLog> {
+        dirs::home_dir().map(|home| TxLog::new(home.join(DEFAULT_TX_LOG_DIR)))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn file_path(&self) -> PathBuf {
+        self.dir.join(TX_LOG_FILE_NAME)
+    }
+
+    /// Appends a record, creating the log directory and file if they don't exist
+    pub fn append(&self, record: &TxLogRecord) -> Result<(), TxError> {
+        if !self.dir.exists() {
+            fs::create_dir_all(&self.dir).map_err(|e| TxError::Logging(e.to_string()))?;
+            fs::set_permissions(
+                &self.dir,
+                fs::Permissions::from_mode(TX_LOG_DIR_PERMISSIONS),
+            )
+            .map_err(|e| TxError::Logging(e.to_string()))?;
+        }
+
+        let mut line =
+            serde_json::to_string(record).map_err(|e| TxError::Logging(e.to_string()))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .mode(TX_LOG_FILE_PERMISSIONS)
+            .open(self.file_path())
+            .map_err(|e| TxError::Logging(e.to_string()))?;
+
+        file.write_all(line.as_bytes())
+            .map_err(|e| TxError::Logging(e.to_string()))
+    }
+
+    /// Returns all records in the order they were logged
+    pub fn records(&self) -> Result<Vec<TxLogRecord>, TxError> {
+        self.list(&TxLogFilter::default())
+    }
+
+    /// Returns the records matching `filter` in the order they were logged
+    pub fn list(&self, filter: &TxLogFilter) -> Result<Vec<TxLogRecord>, TxError> {
+        let file = match File::open(self.file_path()) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(TxError::Logging(e.to_string())),
+        };
+        let mut records = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| TxError::Logging(e.to_string()))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: TxLogRecord =
+                serde_json::from_str(&line).map_err(|e| TxError::Logging(e.to_string()))?;
+
+            if filter.matches(&record) {
+                records.push(record);
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(chain_id: &str, signer: &str, timestamp: u64, status: TxStatus) -> TxLogRecord {
+        TxLogRecord {
+            timestamp,
+            chain_id: chain_id.to_string(),
+            signer: Some(signer.to_string()),
+            msg_type_urls: vec!["/cosmos.bank.v1beta1.MsgSend".to_string()],
+            hash: "ABCD".to_string(),
+            height: Some(10),
+            gas_wanted: 200000,
+            gas_used: 100000,
+            fee: vec![Coin {
+                amount: 5000,
+                denom: "uatom".to_string(),
+            }],
+            status,
+            code: 0,
+            codespace: String::new(),
+        }
+    }
+
+    #[test]
+    fn appends_and_filters_records() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let tx_log = TxLog::new(&dir);
+
+        assert!(tx_log.records().unwrap().is_empty());
+
+        tx_log
+            .append(&record("cosmoshub-4", "cosmos1a", 100, TxStatus::Success))
+            .unwrap();
+        tx_log
+            .append(&record("cosmoshub-4", "cosmos1b", 200, TxStatus::Failed))
+            .unwrap();
+        tx_log
+            .append(&record("osmosis-1", "osmo1a", 300, TxStatus::Pending))
+            .unwrap();
+
+        assert_eq!(tx_log.records().unwrap().len(), 3);
+        assert_eq!(
+            tx_log
+                .list(&TxLogFilter::default().with_chain_id("cosmoshub-4"))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            tx_log
+                .list(&TxLogFilter::default().with_signer("cosmos1b"))
+                .unwrap()[0]
+                .timestamp,
+            200
+        );
+        assert_eq!(
+            tx_log
+                .list(&TxLogFilter::default().with_time_range(Some(150), Some(300)))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            tx_log
+                .list(
+                    &TxLogFilter::default()
+                        .with_chain_id("cosmoshub-4")
+                        .with_status(TxStatus::Pending)
+                )
+                .unwrap()
+                .len(),
+            0
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
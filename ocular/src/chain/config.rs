@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::tx::Coin;
+use crate::tx::GasPrice;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ChainClientConfig {
@@ -12,6 +12,35 @@ pub struct ChainClientConfig {
     #[serde(rename = "grpc-addr")]
     pub grpc_address: String,
     pub account_prefix: String,
+    /// Multiplier applied to the simulated gas usage of a tx to determine its gas limit
     pub gas_adjustment: f64,
-    pub default_fee: Coin,
+    /// Prices per unit of gas, e.g. `["0.025uatom"]`. A tx without an explicit fee pays `gas_limit * price` in
+    /// each of these denoms. Defaults to the chain registry's fee token.
+    #[serde(default)]
+    pub gas_prices: Vec<GasPrice>,
+    /// Timeout height set on txs by [`crate::chain::client::ChainClient::get_basic_tx_metadata`]
+    #[serde(default)]
+    pub timeout_height: TimeoutHeight,
+}
+
+/// Number of blocks after the latest height that txs time out by default
+pub const DEFAULT_TIMEOUT_HEIGHT_DELTA: u64 = 30;
+
+/// How the timeout height of a tx is determined. A tx that hasn't been included in a block by its timeout height
+/// is rejected.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutHeight {
+    /// Number of blocks after the latest height
+    Delta(u64),
+    /// A fixed block height
+    Absolute(u64),
+    /// The tx never times out
+    None,
+}
+
+impl Default for TimeoutHeight {
+    fn default() -> TimeoutHeight {
+        TimeoutHeight::Delta(DEFAULT_TIMEOUT_HEIGHT_DELTA)
+    }
 }
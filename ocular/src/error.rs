@@ -1,7 +1,10 @@
 use cosmrs::{self, ErrorReport};
-use std::{io, num::ParseIntError};
+use std::{fmt, io, num::ParseIntError};
 use thiserror::Error;
 
+/// Codespace of errors defined in the Cosmos SDK's `types/errors` package
+const SDK_CODESPACE: &str = "sdk";
+
 // Higher level errors: ChainClientError, ChainInfoError, ChainRegistryError
 #[derive(Debug, Error)]
 pub enum ChainClientError {
@@ -126,12 +129,93 @@ pub enum TxError {
     TypeConversion(String),
     #[error("error signing message: {0}")]
     Signing(String),
+    #[error("error simulating tx: {0}")]
+    Simulation(String),
     #[error("error broadcasting message: {0}")]
     Broadcast(String),
+    #[error("timed out waiting for tx: {0}")]
+    Timeout(String),
     #[error("error logging response: {0}")]
     Logging(String),
+    #[error("missing fee: {0}")]
+    MissingFee(String),
     #[error("error parsing amount")]
     AmountParse(#[from] ParseIntError),
+    #[error("account sequence mismatch: {0}")]
+    SequenceMismatch(AbciFailure),
+    #[error("unauthorized: {0}")]
+    Unauthorized(AbciFailure),
+    #[error("insufficient funds: {0}")]
+    InsufficientFunds(AbciFailure),
+    #[error("out of gas: {0}")]
+    OutOfGas(AbciFailure),
+    #[error("insufficient fee: {0}")]
+    InsufficientFee(AbciFailure),
+    #[error("tx already exists in mempool cache: {0}")]
+    TxInMempoolCache(AbciFailure),
+    #[error("timeout height reached: {0}")]
+    TimeoutHeight(AbciFailure),
+    #[error("tx failed: {0}")]
+    Failed(AbciFailure),
+}
+
+impl TxError {
+    /// Maps an ABCI codespace/code pair to a typed error. Codes outside of the SDK's codespace,
+    /// or that don't have a dedicated variant, map to [`TxError::Failed`].
+    ///
+    /// Sync broadcast responses don't include a codespace, so an empty codespace is treated as the SDK's. Their
+    /// CheckTx failures come from the SDK's ante handler.
+    pub fn from_abci(failure: AbciFailure) -> TxError {
+        if failure.codespace != SDK_CODESPACE && !failure.codespace.is_empty() {
+            return TxError::Failed(failure);
+        }
+
+        // https://github.com/cosmos/cosmos-sdk/blob/v0.45.4/types/errors/errors.go
+        match failure.code {
+            3 | 32 => TxError::SequenceMismatch(failure),
+            4 => TxError::Unauthorized(failure),
+            5 => TxError::InsufficientFunds(failure),
+            11 => TxError::OutOfGas(failure),
+            13 => TxError::InsufficientFee(failure),
+            19 => TxError::TxInMempoolCache(failure),
+            30 => TxError::TimeoutHeight(failure),
+            _ => TxError::Failed(failure),
+        }
+    }
+
+    /// Returns the underlying ABCI failure if this error came from a failed CheckTx or DeliverTx
+    pub fn abci_failure(&self) -> Option<&AbciFailure> {
+        match self {
+            TxError::SequenceMismatch(f)
+            | TxError::Unauthorized(f)
+            | TxError::InsufficientFunds(f)
+            | TxError::OutOfGas(f)
+            | TxError::InsufficientFee(f)
+            | TxError::TxInMempoolCache(f)
+            | TxError::TimeoutHeight(f)
+            | TxError::Failed(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
+/// Details of a tx that failed during CheckTx or DeliverTx, including the raw log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbciFailure {
+    pub hash: String,
+    pub codespace: String,
+    pub code: u32,
+    pub log: String,
+}
+
+impl fmt::Display for AbciFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tx {} failed with codespace {} code {}: {}",
+            self.hash, self.codespace, self.code, self.log
+        )
+    }
 }
 
 impl From<bech32::Error> for TxError {
@@ -177,4 +261,64 @@ pub enum AccountError {
     Decode(#[from] ErrorReport),
     #[error("invalid key type")]
     InvalidPublicKey(String),
+    #[error("invalid multisig: {0}")]
+    InvalidMultisig(String),
+    #[error("unsupported account type: {0}")]
+    UnsupportedType(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(codespace: &str, code: u32) -> AbciFailure {
+        AbciFailure {
+            hash: "ABCD".to_string(),
+            codespace: codespace.to_string(),
+            code,
+            log: "raw log".to_string(),
+        }
+    }
+
+    #[test]
+    fn maps_abci_codes() {
+        assert!(matches!(
+            TxError::from_abci(failure("sdk", 32)),
+            TxError::SequenceMismatch(_)
+        ));
+        assert!(matches!(
+            TxError::from_abci(failure("sdk", 5)),
+            TxError::InsufficientFunds(_)
+        ));
+        assert!(matches!(
+            TxError::from_abci(failure("sdk", 11)),
+            TxError::OutOfGas(_)
+        ));
+        assert!(matches!(
+            TxError::from_abci(failure("sdk", 4)),
+            TxError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            TxError::from_abci(failure("sdk", 999)),
+            TxError::Failed(_)
+        ));
+        // same code in a different codespace is not an SDK error
+        assert!(matches!(
+            TxError::from_abci(failure("bank", 5)),
+            TxError::Failed(_)
+        ));
+        // sync broadcasts have no codespace
+        assert!(matches!(
+            TxError::from_abci(failure("", 32)),
+            TxError::SequenceMismatch(_)
+        ));
+    }
+
+    #[test]
+    fn keeps_raw_log() {
+        let err = TxError::from_abci(failure("sdk", 13));
+
+        assert_eq!(err.abci_failure().unwrap().log, "raw log");
+        assert!(TxError::Timeout("".to_string()).abci_failure().is_none());
+    }
 }
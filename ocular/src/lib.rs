@@ -19,6 +19,9 @@ pub mod cosmos_modules {
     pub use cosmrs::proto::cosmos::params::v1beta1 as params;
     pub use cosmrs::proto::cosmos::slashing::v1beta1 as slashing;
     pub use cosmrs::proto::cosmos::staking::v1beta1 as staking;
+    pub use cosmrs::proto::cosmos::vesting::v1beta1 as vesting;
+    #[cfg(feature = "cosmwasm")]
+    pub use cosmrs::proto::cosmwasm::wasm::v1 as wasm;
 }
 pub mod error;
 pub(crate) mod github;
@@ -1,6 +1,8 @@
 /// Contains models for serializing and deserializing `assets.json` for a given chain
 use serde::{Deserialize, Serialize};
 
+use crate::{cosmos_modules::bank, error::TxError, tx::Coin};
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AssetList {
@@ -8,6 +10,37 @@ pub struct AssetList {
     pub assets: Vec<Asset>,
 }
 
+impl AssetList {
+    /// Finds the asset with a denom unit, alias or symbol matching `denom`, ignoring case
+    pub fn find(&self, denom: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.unit(denom).is_some())
+    }
+
+    /// Finds the asset whose base denom is `base`
+    pub fn find_by_base(&self, base: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.base == base)
+    }
+
+    /// Parses a human readable amount like `"1.5atom"` or `"250 OSMO"` into a coin of the matching asset's base
+    /// denom
+    pub fn parse_amount(&self, amount: &str) -> Result<Coin, TxError> {
+        let (_, denom) = split_amount(amount)?;
+        let asset = self
+            .find(denom)
+            .ok_or_else(|| TxError::TypeConversion(format!("unknown denom {}", denom)))?;
+
+        asset.parse_amount(amount)
+    }
+
+    /// Formats a base denom coin in its asset's display units. Coins of unknown denoms are formatted as is.
+    pub fn format_amount(&self, coin: &Coin) -> Result<String, TxError> {
+        match self.find_by_base(&coin.denom) {
+            Some(asset) => asset.format_amount(coin.amount),
+            None => Ok(coin.to_string()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Asset {
@@ -22,11 +55,115 @@ pub struct Asset {
     pub coingecko_id: String,
 }
 
+impl Asset {
+    /// Finds the denom unit matching `denom` by name or alias, ignoring case. The asset's symbol matches its
+    /// display unit.
+    pub fn unit(&self, denom: &str) -> Option<&DenomUnit> {
+        let denom = if !self.symbol.is_empty() && self.symbol.eq_ignore_ascii_case(denom) {
+            &self.display
+        } else {
+            denom
+        };
+
+        self.denom_units.iter().find(|u| {
+            u.denom.eq_ignore_ascii_case(denom)
+                || u.aliases.iter().any(|a| a.eq_ignore_ascii_case(denom))
+        })
+    }
+
+    /// Exponent of the display unit. Falls back to 0 (the base unit) if the display unit isn't listed.
+    pub fn display_exponent(&self) -> u16 {
+        self.unit(&self.display).map_or(0, |u| u.exponent)
+    }
+
+    /// Parses a human readable amount like `"1.5atom"` or `"250 ATOM"` into a coin of the base denom. Errors if the
+    /// denom isn't one of the asset's units or the amount has more decimal places than the unit's exponent.
+    pub fn parse_amount(&self, amount: &str) -> Result<Coin, TxError> {
+        let (number, denom) = split_amount(amount)?;
+        let unit = self.unit(denom).ok_or_else(|| {
+            TxError::TypeConversion(format!("{} is not a unit of {}", denom, self.base))
+        })?;
+        let exponent = unit.exponent as usize;
+        let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(TxError::TypeConversion(format!("{} has no amount", amount)));
+        }
+        if fraction.len() > exponent {
+            return Err(TxError::TypeConversion(format!(
+                "{} has more than {} decimal places",
+                amount, exponent
+            )));
+        }
+
+        let digits = format!(
+            "{}{}{:0<width$}",
+            integer,
+            fraction,
+            "",
+            width = exponent - fraction.len()
+        );
+
+        Ok(Coin::new(digits.parse()?, &self.base))
+    }
+
+    /// Formats an amount of the base denom in display units, e.g. `1500000` uatom as `"1.5atom"`. Errors if the
+    /// display unit's exponent is too large for a u128 amount.
+    pub fn format_amount(&self, amount: u128) -> Result<String, TxError> {
+        let exponent = self.display_exponent() as u32;
+        let denom = if self.display.is_empty() {
+            &self.base
+        } else {
+            &self.display
+        };
+
+        if exponent == 0 {
+            return Ok(format!("{}{}", amount, denom));
+        }
+
+        let scale = 10u128.checked_pow(exponent).ok_or_else(|| {
+            TxError::TypeConversion(format!("exponent {} of {} is too large", exponent, denom))
+        })?;
+        let fraction = format!("{:0>width$}", amount % scale, width = exponent as usize);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            Ok(format!("{}{}", amount / scale, denom))
+        } else {
+            Ok(format!("{}.{}{}", amount / scale, fraction, denom))
+        }
+    }
+}
+
+/// Denom metadata registered in the bank module carries the same units as the chain registry
+impl From<bank::Metadata> for Asset {
+    fn from(metadata: bank::Metadata) -> Asset {
+        Asset {
+            description: metadata.description,
+            denom_units: metadata
+                .denom_units
+                .into_iter()
+                .map(|u| DenomUnit {
+                    denom: u.denom,
+                    exponent: u.exponent as u16,
+                    aliases: u.aliases,
+                })
+                .collect(),
+            base: metadata.base,
+            name: metadata.name,
+            display: metadata.display,
+            symbol: metadata.symbol,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DenomUnit {
     pub denom: String,
     pub exponent: u16,
+    pub aliases: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -35,3 +172,146 @@ pub struct LogoURIs {
     pub png: String,
     pub svg: String,
 }
+
+/// Splits an amount like `"1.5 atom"` into its number and denom
+pub(crate) fn split_amount(amount: &str) -> Result<(&str, &str), TxError> {
+    let amount = amount.trim();
+    let split = amount
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| TxError::TypeConversion(format!("{} has no denom", amount)))?;
+    let (number, denom) = amount.split_at(split);
+
+    Ok((number, denom.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atom() -> Asset {
+        Asset {
+            denom_units: vec![
+                DenomUnit {
+                    denom: "uatom".to_string(),
+                    exponent: 0,
+                    aliases: vec!["microatom".to_string()],
+                },
+                DenomUnit {
+                    denom: "matom".to_string(),
+                    exponent: 3,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "atom".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ],
+            base: "uatom".to_string(),
+            display: "atom".to_string(),
+            symbol: "ATOM".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_amounts() {
+        let asset = atom();
+
+        assert_eq!(
+            asset.parse_amount("1.5atom").unwrap(),
+            Coin::new(1500000, "uatom")
+        );
+        assert_eq!(
+            asset.parse_amount(" 250 ATOM ").unwrap(),
+            Coin::new(250000000, "uatom")
+        );
+        assert_eq!(
+            asset.parse_amount(".25matom").unwrap(),
+            Coin::new(250, "uatom")
+        );
+        assert_eq!(
+            asset.parse_amount("42microatom").unwrap(),
+            Coin::new(42, "uatom")
+        );
+        assert_eq!(
+            asset.parse_amount("0.0atom").unwrap(),
+            Coin::new(0, "uatom")
+        );
+        assert!(asset.parse_amount("1.0000001atom").is_err());
+        assert!(asset.parse_amount("1.5uatom").is_err());
+        assert!(asset.parse_amount("1.5osmo").is_err());
+        assert!(asset.parse_amount("atom").is_err());
+        assert!(asset.parse_amount("1.2.3atom").is_err());
+
+        let list = AssetList {
+            chain_name: "cosmoshub".to_string(),
+            assets: vec![asset],
+        };
+
+        assert_eq!(
+            list.parse_amount("2 Atom").unwrap(),
+            Coin::new(2000000, "uatom")
+        );
+        assert!(list.parse_amount("2 osmo").is_err());
+    }
+
+    #[test]
+    fn formats_amounts() {
+        let asset = atom();
+
+        assert_eq!(asset.format_amount(1500000).unwrap(), "1.5atom");
+        assert_eq!(asset.format_amount(250000000).unwrap(), "250atom");
+        assert_eq!(asset.format_amount(1).unwrap(), "0.000001atom");
+        assert_eq!(asset.format_amount(0).unwrap(), "0atom");
+
+        let list = AssetList {
+            chain_name: "cosmoshub".to_string(),
+            assets: vec![asset],
+        };
+
+        assert_eq!(
+            list.format_amount(&Coin::new(1234567, "uatom")).unwrap(),
+            "1.234567atom"
+        );
+        assert_eq!(
+            list.format_amount(&Coin::new(5, "uosmo")).unwrap(),
+            "5uosmo"
+        );
+
+        let mut asset = atom();
+        asset.denom_units[2].exponent = 39;
+
+        assert!(asset.format_amount(1).is_err());
+    }
+
+    #[test]
+    fn converts_denom_metadata() {
+        let metadata = bank::Metadata {
+            description: String::new(),
+            denom_units: vec![
+                bank::DenomUnit {
+                    denom: "uosmo".to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                bank::DenomUnit {
+                    denom: "osmo".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ],
+            base: "uosmo".to_string(),
+            display: "osmo".to_string(),
+            name: "Osmosis".to_string(),
+            symbol: "OSMO".to_string(),
+        };
+        let asset = Asset::from(metadata);
+
+        assert_eq!(
+            asset.parse_amount("250 OSMO").unwrap(),
+            Coin::new(250000000, "uosmo")
+        );
+        assert_eq!(asset.format_amount(1000).unwrap(), "0.001osmo");
+    }
+}
@@ -1,7 +1,11 @@
 /// Provides caching of registry data for easy querying and filtering.
 use crate::{
     error::ChainRegistryError,
-    registry::{self, paths::IBCPath},
+    registry::{
+        self,
+        paths::{IBCPath, TransferChannel},
+        ChainInfo,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, collections::HashMap};
@@ -10,14 +14,31 @@ use super::paths::Tag;
 
 // TO-DO:
 // - Option to load from local repo clone
-// - Currently don't see a need to cache chain/asset info but might need it in the future
-/// Used to cache IBC path data from the chain registry for easy filtering.
+// - Currently don't see a need to cache asset info but might need it in the future
+/// Used to cache IBC path data from the chain registry for easy filtering. Chain info can be added for chains that
+/// are looked up often or aren't in the registry, such as local test networks.
 #[derive(Default, Deserialize, Serialize)]
 pub struct RegistryCache {
     paths: HashMap<String, IBCPath>,
+    #[serde(default)]
+    chains: HashMap<String, ChainInfo>,
 }
 
 impl RegistryCache {
+    /// Returns the cached [`ChainInfo`] for `name`, or fetches it from the chain registry if it hasn't been added
+    pub async fn get_chain(&self, name: &str) -> Result<Option<ChainInfo>, ChainRegistryError> {
+        if let Some(info) = self.chains.get(name) {
+            return Ok(Some(info.clone()));
+        }
+
+        registry::get_chain(name).await
+    }
+
+    /// Caches `info` under its `chain_name`, replacing any chain info already cached for it
+    pub fn add_chain(&mut self, info: ChainInfo) {
+        self.chains.insert(info.chain_name.clone(), info);
+    }
+
     /// Returns a cached [`IBCPath`] representing a channel between `chain_a` and `chain_b` if it exists.
     /// Passing in the same value for `chain_a` and `chain_b` will always return `Ok(None)`.
     ///
@@ -39,6 +60,18 @@ impl RegistryCache {
         Ok(self.paths.get(&path_name).cloned())
     }
 
+    /// Returns `source_chain`'s end of the preferred live transfer channel to `destination_chain`, if one exists
+    pub async fn get_transfer_channel(
+        &self,
+        source_chain: &str,
+        destination_chain: &str,
+    ) -> Result<Option<TransferChannel>, ChainRegistryError> {
+        Ok(self
+            .get_path(source_chain, destination_chain)
+            .await?
+            .and_then(|p| p.transfer_channel(source_chain)))
+    }
+
     /// Returns cached [`IBCPath`] that match a provided [`Tag`]
     ///
     /// # Arguments
@@ -89,6 +122,9 @@ impl RegistryCache {
             );
         }
 
-        Ok(RegistryCache { paths })
+        Ok(RegistryCache {
+            paths,
+            chains: HashMap::default(),
+        })
     }
 }
@@ -4,6 +4,7 @@ use crate::{
     chain::{client, config::ChainClientConfig},
     error::{ChainInfoError, RpcError},
     registry::{self, assets::AssetList},
+    tx::GasPrice,
 };
 use futures::executor;
 use rand::{prelude::SliceRandom, thread_rng};
@@ -27,6 +28,7 @@ pub struct ChainInfo {
     pub codebase: Codebase,
     pub peers: Peers,
     pub apis: Apis,
+    pub fees: Fees,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -76,6 +78,35 @@ pub struct Apis {
     pub grpc: Vec<Grpc>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Fees {
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub fee_tokens: Vec<FeeToken>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FeeToken {
+    pub denom: String,
+    pub fixed_min_gas_price: Option<f64>,
+    pub low_gas_price: Option<f64>,
+    pub average_gas_price: Option<f64>,
+    pub high_gas_price: Option<f64>,
+}
+
+impl FeeToken {
+    /// The average gas price, falling back to the low and then the fixed minimum gas price
+    pub fn gas_price(&self) -> Option<GasPrice> {
+        let price = self
+            .average_gas_price
+            .or(self.low_gas_price)
+            .or(self.fixed_min_gas_price)?;
+
+        format!("{}{}", price, self.denom).parse().ok()
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Rpc {
@@ -126,6 +157,14 @@ impl ChainInfo {
             account_prefix: self.bech32_prefix.clone(),
             chain_id: self.chain_id.clone(),
             gas_adjustment: 1.2,
+            // Only the first fee token is used so fees aren't paid in every accepted denom
+            gas_prices: self
+                .fees
+                .fee_tokens
+                .first()
+                .and_then(FeeToken::gas_price)
+                .into_iter()
+                .collect(),
             rpc_address,
             ..Default::default()
         })
@@ -203,6 +242,35 @@ mod tests {
         assets.unwrap();
     }
 
+    #[test]
+    fn fee_token_gas_price() {
+        let token = FeeToken {
+            denom: "uatom".to_string(),
+            fixed_min_gas_price: Some(0.0),
+            low_gas_price: Some(0.01),
+            average_gas_price: Some(0.025),
+            high_gas_price: Some(0.03),
+        };
+
+        assert_eq!(token.gas_price().unwrap().to_string(), "0.025uatom");
+        assert_eq!(
+            FeeToken {
+                average_gas_price: None,
+                ..token.clone()
+            }
+            .gas_price()
+            .unwrap()
+            .to_string(),
+            "0.01uatom"
+        );
+        assert!(FeeToken {
+            denom: "uatom".to_string(),
+            ..Default::default()
+        }
+        .gas_price()
+        .is_none());
+    }
+
     #[assay]
     async fn build_chain_config() {
         // as a unit test this shouldn't really rely on other parts
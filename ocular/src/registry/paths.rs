@@ -2,6 +2,9 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+/// Port bound by the ICS-20 fungible token transfer module
+pub const TRANSFER_PORT: &str = "transfer";
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct IBCPath {
@@ -14,6 +17,34 @@ pub struct IBCPath {
     pub channels: Vec<Channel>,
 }
 
+impl IBCPath {
+    /// Returns `chain_name`'s end of the path's live ICS-20 transfer channel, preferring channels tagged as
+    /// preferred. Returns `None` if no transfer channel is live, since transfers over closed channels fail.
+    pub fn transfer_channel(&self, chain_name: &str) -> Option<TransferChannel> {
+        let ends = self.channels.iter().filter_map(|c| {
+            let (port_id, channel_id) = if self.chain_1.chain_name == chain_name {
+                (&c.chain_1.port_id, &c.chain_1.channel_id)
+            } else if self.chain_2.chain_name == chain_name {
+                (&c.chain_2.port_id, &c.chain_2.channel_id)
+            } else {
+                return None;
+            };
+
+            if port_id != TRANSFER_PORT || c.tags.status != "live" {
+                return None;
+            }
+
+            Some((c, port_id, channel_id))
+        });
+
+        ends.max_by_key(|(c, _, _)| c.tags.preferred)
+            .map(|(_, port_id, channel_id)| TransferChannel {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            })
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Chain1 {
@@ -82,3 +113,95 @@ pub enum Tag {
     Properties(String),
     Status(String),
 }
+
+/// One end of an ICS-20 transfer channel
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TransferChannel {
+    pub port_id: String,
+    pub channel_id: String,
+}
+
+impl TransferChannel {
+    /// A channel bound to the transfer port
+    pub fn new(channel_id: &str) -> TransferChannel {
+        TransferChannel {
+            port_id: TRANSFER_PORT.to_string(),
+            channel_id: channel_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(chain_1: &str, chain_2: &str, preferred: bool, status: &str) -> Channel {
+        Channel {
+            chain_1: ChannelChain1 {
+                channel_id: chain_1.to_string(),
+                port_id: TRANSFER_PORT.to_string(),
+            },
+            chain_2: ChannelChain2 {
+                channel_id: chain_2.to_string(),
+                port_id: TRANSFER_PORT.to_string(),
+            },
+            tags: Tags {
+                preferred,
+                status: status.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn selects_transfer_channel() {
+        let mut path = IBCPath {
+            chain_1: Chain1 {
+                chain_name: "cosmoshub".to_string(),
+                ..Default::default()
+            },
+            chain_2: Chain2 {
+                chain_name: "osmosis".to_string(),
+                ..Default::default()
+            },
+            channels: vec![
+                channel("channel-3", "channel-1", false, "killed"),
+                channel("channel-141", "channel-0", true, "live"),
+                channel("channel-7", "channel-9", false, "live"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            path.transfer_channel("cosmoshub"),
+            Some(TransferChannel::new("channel-141"))
+        );
+        assert_eq!(
+            path.transfer_channel("osmosis"),
+            Some(TransferChannel::new("channel-0"))
+        );
+        assert_eq!(path.transfer_channel("juno"), None);
+
+        path.channels[1].chain_1.port_id = "wasm.juno1contract".to_string();
+
+        assert_eq!(
+            path.transfer_channel("cosmoshub"),
+            Some(TransferChannel::new("channel-7"))
+        );
+
+        path.channels = vec![
+            channel("channel-3", "channel-1", true, "killed"),
+            channel("channel-7", "channel-9", false, "live"),
+        ];
+
+        assert_eq!(
+            path.transfer_channel("cosmoshub"),
+            Some(TransferChannel::new("channel-7"))
+        );
+
+        path.channels.truncate(1);
+
+        assert_eq!(path.transfer_channel("cosmoshub"), None);
+    }
+}
@@ -1,79 +1,100 @@
-use cosmrs::{AccountId, Denom};
+use cosmos_sdk_proto::cosmos::{
+    bank::v1beta1 as bank, base::v1beta1::Coin as ProtoCoin,
+    tx::signing::v1beta1::SignMode as ProtoSignMode,
+};
+use cosmrs::AccountId;
 use serde::{Deserialize, Serialize};
 
 use crate::error::TxError;
 
+pub use self::builder::*;
+pub use self::coin::*;
+pub use self::decode::*;
+pub use self::multisig::*;
 pub use prost_types::Any;
 
+pub mod amino;
+pub mod builder;
+pub mod coin;
+pub mod decode;
+pub mod multisig;
+
 /// Metadata wrapper for transactions
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct TxMetadata {
-    pub fee: Coin,
+    /// May contain multiple coins. If empty, the fee is computed from the client config's `gas_prices` once the
+    /// gas limit is known.
+    #[serde(default)]
+    pub fee: Vec<Coin>,
     pub fee_payer: Option<AccountId>,
     pub fee_granter: Option<AccountId>,
+    /// A gas limit of 0 will cause the gas to be estimated by simulating the tx. See [`crate::chain::client::ChainClient::simulate_tx`].
     pub gas_limit: u64,
     pub timeout_height: u32,
     #[serde(default)]
     pub memo: String,
+    #[serde(default)]
+    pub broadcast_mode: BroadcastMode,
+    /// By default a tx that fails CheckTx or DeliverTx results in a [`TxError`]. Set to true to
+    /// receive the raw response instead.
+    #[serde(default)]
+    pub allow_failed_tx: bool,
+    #[serde(default)]
+    pub sign_mode: SignMode,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct Coin {
-    pub amount: u64,
-    pub denom: String,
-}
-
-impl From<Coin> for cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-    fn from(coin: Coin) -> Self {
-        cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-            amount: coin.amount.to_string(),
-            denom: coin.denom,
-        }
-    }
-}
-
-impl TryFrom<Coin> for cosmrs::Coin {
-    type Error = TxError;
-
-    fn try_from(coin: Coin) -> Result<cosmrs::Coin, Self::Error> {
-        cosmrs::Coin::try_from(&coin)
-    }
+/// Determines how long a broadcast call waits on the node before returning.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum BroadcastMode {
+    /// Returns immediately without waiting for the tx to be checked
+    Async,
+    /// Returns after the tx has passed (or failed) CheckTx
+    Sync,
+    /// Returns after the tx has been committed in a block, or the node's RPC times out
+    #[default]
+    Commit,
 }
 
-impl TryFrom<&Coin> for cosmrs::Coin {
-    type Error = TxError;
-
-    fn try_from(coin: &Coin) -> Result<cosmrs::Coin, Self::Error> {
-        Ok(cosmrs::Coin {
-            denom: coin.denom.parse::<Denom>()?,
-            amount: coin.amount.into(),
-        })
-    }
+/// Determines which bytes a signer signs. See [`UnsignedTx::sign_bytes`].
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum SignMode {
+    /// Signs the protobuf encoded `SignDoc`
+    #[default]
+    Direct,
+    /// Signs the legacy amino JSON `StdSignDoc`. Required by some chains and by amino based tooling such as
+    /// hardware wallets. Only supports the messages in [`amino`].
+    LegacyAminoJson,
 }
 
-impl TryFrom<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> for Coin {
-    type Error = TxError;
-
-    fn try_from(coin: cosmos_sdk_proto::cosmos::base::v1beta1::Coin) -> Result<Coin, Self::Error> {
-        Coin::try_from(&coin)
+impl From<SignMode> for i32 {
+    fn from(mode: SignMode) -> Self {
+        match mode {
+            SignMode::Direct => ProtoSignMode::Direct.into(),
+            SignMode::LegacyAminoJson => ProtoSignMode::LegacyAminoJson.into(),
+        }
     }
 }
 
-impl TryFrom<&cosmos_sdk_proto::cosmos::base::v1beta1::Coin> for Coin {
+impl TryFrom<i32> for SignMode {
     type Error = TxError;
 
-    fn try_from(coin: &cosmos_sdk_proto::cosmos::base::v1beta1::Coin) -> Result<Coin, Self::Error> {
-        Ok(Coin {
-            denom: coin.denom.clone(),
-            amount: coin.amount.parse()?,
-        })
+    fn try_from(mode: i32) -> Result<SignMode, Self::Error> {
+        match ProtoSignMode::from_i32(mode) {
+            Some(ProtoSignMode::Direct) => Ok(SignMode::Direct),
+            Some(ProtoSignMode::LegacyAminoJson) => Ok(SignMode::LegacyAminoJson),
+            _ => Err(TxError::Serialization(format!(
+                "unsupported sign mode {}",
+                mode
+            ))),
+        }
     }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Payment {
     pub recipient: String,
-    pub amount: u64,
+    #[serde(with = "coin::amount")]
+    pub amount: u128,
     pub denom: String,
 }
 
@@ -90,41 +111,34 @@ pub struct MultiSendIo {
     pub coins: Vec<Coin>,
 }
 
-impl TryFrom<MultiSendIo> for cosmrs::bank::MultiSendIo {
-    type Error = TxError;
+impl MultiSendIo {
+    /// The validated address and the coins in their protobuf representation, which holds any u128 amount
+    fn to_proto_parts(&self) -> Result<(String, Vec<ProtoCoin>), TxError> {
+        let address = self.address.as_str().parse::<AccountId>()?;
 
-    fn try_from(value: MultiSendIo) -> Result<cosmrs::bank::MultiSendIo, Self::Error> {
-        cosmrs::bank::MultiSendIo::try_from(&value)
+        Ok((
+            address.to_string(),
+            self.coins.iter().cloned().map(ProtoCoin::from).collect(),
+        ))
     }
 }
 
-impl TryFrom<&MultiSendIo> for cosmrs::bank::MultiSendIo {
+impl TryFrom<&MultiSendIo> for bank::Input {
     type Error = TxError;
 
-    fn try_from(value: &MultiSendIo) -> Result<cosmrs::bank::MultiSendIo, Self::Error> {
-        Ok(cosmrs::bank::MultiSendIo {
-            address: value.address.as_str().parse::<AccountId>()?,
-            coins: value
-                .coins
-                .iter()
-                .map(TryFrom::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+    fn try_from(value: &MultiSendIo) -> Result<bank::Input, Self::Error> {
+        let (address, coins) = value.to_proto_parts()?;
+
+        Ok(bank::Input { address, coins })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl TryFrom<&MultiSendIo> for bank::Output {
+    type Error = TxError;
 
-    #[test]
-    fn converts_coin() {
-        let coin = Coin {
-            amount: 100,
-            denom: "utest".to_string(),
-        };
+    fn try_from(value: &MultiSendIo) -> Result<bank::Output, Self::Error> {
+        let (address, coins) = value.to_proto_parts()?;
 
-        cosmrs::Coin::try_from(&coin).unwrap();
-        cosmrs::Coin::try_from(coin).unwrap();
+        Ok(bank::Output { address, coins })
     }
 }
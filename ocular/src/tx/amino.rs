@@ -0,0 +1,904 @@
+//! Legacy amino JSON encoding of the messages ocular builds, used for [`super::SignMode::LegacyAminoJson`] signing.
+//!
+//! The output matches the Cosmos SDK's `StdSignDoc`: object keys are sorted, empty message fields are omitted,
+//! integers are encoded as strings and timestamps as RFC 3339.
+use cosmos_sdk_proto::cosmos::{
+    authz::v1beta1::{GenericAuthorization, Grant, MsgExec, MsgGrant, MsgRevoke},
+    bank::v1beta1::{Input, MsgMultiSend, MsgSend, Output, SendAuthorization},
+    base::v1beta1::Coin,
+    distribution::v1beta1::{
+        CommunityPoolSpendProposal, MsgFundCommunityPool, MsgSetWithdrawAddress,
+        MsgWithdrawDelegatorReward, MsgWithdrawValidatorCommission,
+    },
+    feegrant::v1beta1::{
+        AllowedMsgAllowance, BasicAllowance, MsgGrantAllowance, MsgRevokeAllowance,
+        PeriodicAllowance,
+    },
+    gov::v1beta1::{
+        MsgDeposit, MsgSubmitProposal, MsgVote, MsgVoteWeighted, TextProposal, WeightedVoteOption,
+    },
+    params::v1beta1::ParameterChangeProposal,
+    slashing::v1beta1::MsgUnjail,
+    staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate},
+    tx::v1beta1::{AuthInfo, TxBody},
+    vesting::v1beta1::MsgCreateVestingAccount,
+};
+#[cfg(feature = "cosmwasm")]
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+};
+use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
+use prost::Message;
+use serde_json::{json, Map, Value};
+
+use crate::{error::TxError, Timestamp};
+
+use super::{Any, UnsignedTx};
+
+/// Converts a protobuf encoded message to its amino JSON representation, `{"type": ..., "value": ...}`.
+pub fn msg_to_json(msg: &Any) -> Result<Value, TxError> {
+    let (amino_type, mut value) = match msg.type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => {
+            let msg: MsgSend = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgSend",
+                json!({
+                    "from_address": msg.from_address,
+                    "to_address": msg.to_address,
+                    "amount": coins(&msg.amount),
+                }),
+            )
+        }
+        "/cosmos.bank.v1beta1.MsgMultiSend" => {
+            let msg: MsgMultiSend = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgMultiSend",
+                json!({
+                    "inputs": msg.inputs.iter().map(input).collect::<Vec<Value>>(),
+                    "outputs": msg.outputs.iter().map(output).collect::<Vec<Value>>(),
+                }),
+            )
+        }
+        "/cosmos.authz.v1beta1.MsgGrant" => {
+            let msg: MsgGrant = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgGrant",
+                json!({
+                    "granter": msg.granter,
+                    "grantee": msg.grantee,
+                    "grant": msg.grant.as_ref().map(grant).transpose()?,
+                }),
+            )
+        }
+        "/cosmos.authz.v1beta1.MsgRevoke" => {
+            let msg: MsgRevoke = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgRevoke",
+                json!({
+                    "granter": msg.granter,
+                    "grantee": msg.grantee,
+                    "msg_type_url": msg.msg_type_url,
+                }),
+            )
+        }
+        "/cosmos.authz.v1beta1.MsgExec" => {
+            let msg: MsgExec = decode(msg)?;
+            let mut value = json!({ "grantee": msg.grantee });
+            let msgs = msg
+                .msgs
+                .iter()
+                .map(msg_to_json)
+                .collect::<Result<Vec<Value>, _>>()?;
+
+            // The inner msgs already had their empty fields omitted, except those of contract msgs which must be
+            // kept
+            omit_empty(&mut value);
+            if !msgs.is_empty() {
+                value["msgs"] = msgs.into();
+            }
+
+            return Ok(json!({ "type": "cosmos-sdk/MsgExec", "value": value }));
+        }
+        "/cosmos.feegrant.v1beta1.MsgGrantAllowance" => {
+            let msg: MsgGrantAllowance = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgGrantAllowance",
+                json!({
+                    "granter": msg.granter,
+                    "grantee": msg.grantee,
+                    "allowance": msg.allowance.as_ref().map(any_to_json).transpose()?,
+                }),
+            )
+        }
+        "/cosmos.feegrant.v1beta1.MsgRevokeAllowance" => {
+            let msg: MsgRevokeAllowance = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgRevokeAllowance",
+                json!({
+                    "granter": msg.granter,
+                    "grantee": msg.grantee,
+                }),
+            )
+        }
+        "/cosmos.staking.v1beta1.MsgDelegate" => {
+            let msg: MsgDelegate = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgDelegate",
+                json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                    "amount": msg.amount.as_ref().map(coin),
+                }),
+            )
+        }
+        "/cosmos.staking.v1beta1.MsgUndelegate" => {
+            let msg: MsgUndelegate = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgUndelegate",
+                json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                    "amount": msg.amount.as_ref().map(coin),
+                }),
+            )
+        }
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => {
+            let msg: MsgBeginRedelegate = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgBeginRedelegate",
+                json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_src_address": msg.validator_src_address,
+                    "validator_dst_address": msg.validator_dst_address,
+                    "amount": msg.amount.as_ref().map(coin),
+                }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => {
+            let msg: MsgWithdrawDelegatorReward = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgWithdrawDelegationReward",
+                json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission" => {
+            let msg: MsgWithdrawValidatorCommission = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgWithdrawValidatorCommission",
+                json!({ "validator_address": msg.validator_address }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress" => {
+            let msg: MsgSetWithdrawAddress = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgModifyWithdrawAddress",
+                json!({
+                    "delegator_address": msg.delegator_address,
+                    "withdraw_address": msg.withdraw_address,
+                }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgFundCommunityPool" => {
+            let msg: MsgFundCommunityPool = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgFundCommunityPool",
+                json!({
+                    "amount": coins(&msg.amount),
+                    "depositor": msg.depositor,
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgSubmitProposal" => {
+            let msg: MsgSubmitProposal = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgSubmitProposal",
+                json!({
+                    "content": msg.content.as_ref().map(any_to_json).transpose()?,
+                    "initial_deposit": coins(&msg.initial_deposit),
+                    "proposer": msg.proposer,
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgDeposit" => {
+            let msg: MsgDeposit = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgDeposit",
+                json!({
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "depositor": msg.depositor,
+                    "amount": coins(&msg.amount),
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => {
+            let msg: MsgVote = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgVote",
+                json!({
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "voter": msg.voter,
+                    "option": msg.option,
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgVoteWeighted" => {
+            let msg: MsgVoteWeighted = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgVoteWeighted",
+                json!({
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "voter": msg.voter,
+                    "options": msg
+                        .options
+                        .iter()
+                        .map(weighted_vote_option)
+                        .collect::<Result<Vec<Value>, _>>()?,
+                }),
+            )
+        }
+        "/cosmos.slashing.v1beta1.MsgUnjail" => {
+            let msg: MsgUnjail = decode(msg)?;
+
+            (
+                "cosmos-sdk/MsgUnjail",
+                json!({ "address": msg.validator_addr }),
+            )
+        }
+        "/cosmos.vesting.v1beta1.MsgCreateVestingAccount" => {
+            let msg: MsgCreateVestingAccount = decode(msg)?;
+            let mut value = json!({
+                "from_address": msg.from_address,
+                "to_address": msg.to_address,
+                "amount": coins(&msg.amount),
+                "end_time": msg.end_time.to_string(),
+            });
+            // omitted when false
+            if msg.delayed {
+                value["delayed"] = true.into();
+            }
+
+            ("cosmos-sdk/MsgCreateVestingAccount", value)
+        }
+        "/ibc.applications.transfer.v1.MsgTransfer" => {
+            let msg: MsgTransfer = decode(msg)?;
+            // zero heights and timestamps are omitted
+            let timeout_height = msg.timeout_height.map(|h| {
+                let mut height = Map::new();
+                if h.revision_number > 0 {
+                    height.insert(
+                        "revision_number".to_string(),
+                        h.revision_number.to_string().into(),
+                    );
+                }
+                if h.revision_height > 0 {
+                    height.insert(
+                        "revision_height".to_string(),
+                        h.revision_height.to_string().into(),
+                    );
+                }
+
+                Value::Object(height)
+            });
+
+            (
+                "cosmos-sdk/MsgTransfer",
+                json!({
+                    "source_port": msg.source_port,
+                    "source_channel": msg.source_channel,
+                    "token": msg.token.as_ref().map(coin),
+                    "sender": msg.sender,
+                    "receiver": msg.receiver,
+                    "timeout_height": timeout_height.unwrap_or_else(|| json!({})),
+                    "timeout_timestamp": (msg.timeout_timestamp > 0)
+                        .then(|| msg.timeout_timestamp.to_string()),
+                }),
+            )
+        }
+        #[cfg(feature = "cosmwasm")]
+        "/cosmwasm.wasm.v1.MsgStoreCode" => {
+            let msg: MsgStoreCode = decode(msg)?;
+
+            if msg.instantiate_permission.is_some() {
+                return Err(TxError::Serialization(
+                    "no amino JSON encoding for MsgStoreCode instantiate permissions".to_string(),
+                ));
+            }
+
+            (
+                "wasm/MsgStoreCode",
+                json!({
+                    "sender": msg.sender,
+                    "wasm_byte_code": base64::encode(&msg.wasm_byte_code),
+                }),
+            )
+        }
+        #[cfg(feature = "cosmwasm")]
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            let msg: MsgInstantiateContract = decode(msg)?;
+            let value = json!({
+                "sender": msg.sender,
+                "admin": msg.admin,
+                "code_id": msg.code_id.to_string(),
+                "label": msg.label,
+                "funds": coins(&msg.funds),
+            });
+
+            return contract_msg_to_json("wasm/MsgInstantiateContract", value, &msg.msg);
+        }
+        #[cfg(feature = "cosmwasm")]
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+            let msg: MsgExecuteContract = decode(msg)?;
+            let value = json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "funds": coins(&msg.funds),
+            });
+
+            return contract_msg_to_json("wasm/MsgExecuteContract", value, &msg.msg);
+        }
+        #[cfg(feature = "cosmwasm")]
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            let msg: MsgMigrateContract = decode(msg)?;
+            let value = json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "code_id": msg.code_id.to_string(),
+            });
+
+            return contract_msg_to_json("wasm/MsgMigrateContract", value, &msg.msg);
+        }
+        _ => return any_to_json(msg),
+    };
+
+    omit_empty(&mut value);
+
+    Ok(json!({ "type": amino_type, "value": value }))
+}
+
+/// Builds the sorted, compact `StdSignDoc` bytes for a tx
+pub fn sign_doc_bytes(unsigned_tx: &UnsignedTx) -> Result<Vec<u8>, TxError> {
+    let body = TxBody::decode(&unsigned_tx.body_bytes[..])
+        .map_err(|e| TxError::Serialization(e.to_string()))?;
+    let auth_info = AuthInfo::decode(&unsigned_tx.auth_info_bytes[..])
+        .map_err(|e| TxError::Serialization(e.to_string()))?;
+    let sequence = auth_info
+        .signer_infos
+        .first()
+        .map(|s| s.sequence)
+        .ok_or_else(|| TxError::Serialization("tx has no signer info".to_string()))?;
+    let fee = auth_info.fee.unwrap_or_default();
+
+    let mut fee_json = Map::new();
+    fee_json.insert("amount".to_string(), coins(&fee.amount));
+    fee_json.insert("gas".to_string(), fee.gas_limit.to_string().into());
+    if !fee.payer.is_empty() {
+        fee_json.insert("payer".to_string(), fee.payer.into());
+    }
+    if !fee.granter.is_empty() {
+        fee_json.insert("granter".to_string(), fee.granter.into());
+    }
+
+    let mut doc = Map::new();
+    doc.insert(
+        "account_number".to_string(),
+        unsigned_tx.account_number.to_string().into(),
+    );
+    doc.insert("chain_id".to_string(), unsigned_tx.chain_id.clone().into());
+    doc.insert("fee".to_string(), Value::Object(fee_json));
+    doc.insert("memo".to_string(), body.memo.into());
+    doc.insert(
+        "msgs".to_string(),
+        Value::Array(
+            body.messages
+                .iter()
+                .map(msg_to_json)
+                .collect::<Result<Vec<Value>, _>>()?,
+        ),
+    );
+    doc.insert("sequence".to_string(), sequence.to_string().into());
+    if body.timeout_height > 0 {
+        doc.insert(
+            "timeout_height".to_string(),
+            body.timeout_height.to_string().into(),
+        );
+    }
+
+    let json = serde_json::to_string(&sorted(Value::Object(doc)))
+        .map_err(|e| TxError::Serialization(e.to_string()))?;
+
+    // Go's JSON encoder escapes HTML characters, and the sign bytes must match exactly
+    Ok(json
+        .replace('&', "\\u0026")
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .into_bytes())
+}
+
+/// Formats a timestamp the way Go's `time.Time` marshals to JSON: UTC with trailing zeros of the fractional
+/// seconds removed.
+pub fn rfc3339(timestamp: &Timestamp) -> String {
+    let days = timestamp.seconds.div_euclid(86400);
+    let seconds = timestamp.seconds.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let mut formatted = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+
+    if timestamp.nanos > 0 {
+        formatted.push('.');
+        formatted.push_str(format!("{:09}", timestamp.nanos).trim_end_matches('0'));
+    }
+    formatted.push('Z');
+
+    formatted
+}
+
+// Converts days since the unix epoch to a (year, month, day) date.
+// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Authorizations and allowances are encoded as `{"type": ..., "value": ...}` like messages
+fn any_to_json(any: &Any) -> Result<Value, TxError> {
+    let (amino_type, mut value) = match any.type_url.as_str() {
+        "/cosmos.authz.v1beta1.GenericAuthorization" => {
+            let authorization: GenericAuthorization = decode(any)?;
+
+            (
+                "cosmos-sdk/GenericAuthorization",
+                json!({ "msg": authorization.msg }),
+            )
+        }
+        "/cosmos.bank.v1beta1.SendAuthorization" => {
+            let authorization: SendAuthorization = decode(any)?;
+
+            (
+                "cosmos-sdk/SendAuthorization",
+                json!({ "spend_limit": coins(&authorization.spend_limit) }),
+            )
+        }
+        "/cosmos.feegrant.v1beta1.BasicAllowance" => {
+            let allowance: BasicAllowance = decode(any)?;
+
+            ("cosmos-sdk/BasicAllowance", basic_allowance(&allowance))
+        }
+        "/cosmos.feegrant.v1beta1.PeriodicAllowance" => {
+            let allowance: PeriodicAllowance = decode(any)?;
+
+            (
+                "cosmos-sdk/PeriodicAllowance",
+                json!({
+                    "basic": allowance.basic.as_ref().map(basic_allowance),
+                    // amino encodes durations as nanoseconds
+                    "period": allowance.period.as_ref().map(|d| {
+                        (i128::from(d.seconds) * 1_000_000_000 + i128::from(d.nanos)).to_string()
+                    }),
+                    "period_spend_limit": coins(&allowance.period_spend_limit),
+                    "period_can_spend": coins(&allowance.period_can_spend),
+                    "period_reset": allowance.period_reset.as_ref().map(rfc3339),
+                }),
+            )
+        }
+        "/cosmos.feegrant.v1beta1.AllowedMsgAllowance" => {
+            let allowance: AllowedMsgAllowance = decode(any)?;
+
+            (
+                "cosmos-sdk/AllowedMsgAllowance",
+                json!({
+                    "allowance": allowance.allowance.as_ref().map(any_to_json).transpose()?,
+                    "allowed_messages": allowance.allowed_messages,
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.TextProposal" => {
+            let proposal: TextProposal = decode(any)?;
+
+            (
+                "cosmos-sdk/TextProposal",
+                json!({ "title": proposal.title, "description": proposal.description }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal" => {
+            let proposal: CommunityPoolSpendProposal = decode(any)?;
+
+            (
+                "cosmos-sdk/CommunityPoolSpendProposal",
+                json!({
+                    "title": proposal.title,
+                    "description": proposal.description,
+                    "recipient": proposal.recipient,
+                    "amount": coins(&proposal.amount),
+                }),
+            )
+        }
+        "/cosmos.params.v1beta1.ParameterChangeProposal" => {
+            let proposal: ParameterChangeProposal = decode(any)?;
+
+            (
+                "cosmos-sdk/ParameterChangeProposal",
+                json!({
+                    "title": proposal.title,
+                    "description": proposal.description,
+                    "changes": proposal
+                        .changes
+                        .iter()
+                        .map(|c| json!({ "subspace": c.subspace, "key": c.key, "value": c.value }))
+                        .collect::<Vec<Value>>(),
+                }),
+            )
+        }
+        type_url => {
+            return Err(TxError::Serialization(format!(
+                "no amino JSON encoding for {}",
+                type_url
+            )))
+        }
+    };
+
+    omit_empty(&mut value);
+
+    Ok(json!({ "type": amino_type, "value": value }))
+}
+
+fn decode<M: Message + Default>(any: &Any) -> Result<M, TxError> {
+    M::decode(&any.value[..]).map_err(|e| TxError::Serialization(e.to_string()))
+}
+
+fn coin(coin: &Coin) -> Value {
+    json!({ "amount": coin.amount, "denom": coin.denom })
+}
+
+fn coins(coins: &[Coin]) -> Value {
+    coins.iter().map(coin).collect()
+}
+
+fn input(input: &Input) -> Value {
+    json!({ "address": input.address, "coins": coins(&input.coins) })
+}
+
+fn output(output: &Output) -> Value {
+    json!({ "address": output.address, "coins": coins(&output.coins) })
+}
+
+fn basic_allowance(allowance: &BasicAllowance) -> Value {
+    json!({
+        "spend_limit": coins(&allowance.spend_limit),
+        "expiration": allowance.expiration.as_ref().map(rfc3339),
+    })
+}
+
+/// Contract messages are embedded as raw JSON, which must not have its empty fields omitted
+#[cfg(feature = "cosmwasm")]
+fn contract_msg_to_json(
+    amino_type: &str,
+    mut value: Value,
+    contract_msg: &[u8],
+) -> Result<Value, TxError> {
+    omit_empty(&mut value);
+    value["msg"] =
+        serde_json::from_slice(contract_msg).map_err(|e| TxError::Serialization(e.to_string()))?;
+
+    Ok(json!({ "type": amino_type, "value": value }))
+}
+
+/// Amino encodes decimals with all 18 decimal places, e.g. `"0.500000000000000000"`
+fn weighted_vote_option(option: &WeightedVoteOption) -> Result<Value, TxError> {
+    let weight: u128 = option
+        .weight
+        .parse()
+        .map_err(|_| TxError::Serialization(format!("invalid vote weight {}", option.weight)))?;
+    let weight = format!("{:019}", weight);
+    let (integer, fraction) = weight.split_at(weight.len() - 18);
+
+    Ok(json!({
+        "option": option.option,
+        "weight": format!("{}.{}", integer, fraction),
+    }))
+}
+
+fn grant(grant: &Grant) -> Result<Value, TxError> {
+    Ok(json!({
+        "authorization": grant.authorization.as_ref().map(any_to_json).transpose()?,
+        "expiration": grant.expiration.as_ref().map(rfc3339),
+    }))
+}
+
+/// Removes null, empty string and empty array fields, like Go's `omitempty`
+fn omit_empty(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| match v {
+                Value::Null => false,
+                Value::String(s) => !s.is_empty(),
+                Value::Array(a) => !a.is_empty(),
+                _ => true,
+            });
+            map.values_mut().for_each(omit_empty);
+        }
+        Value::Array(values) => values.iter_mut().for_each(omit_empty),
+        _ => (),
+    }
+}
+
+/// Sorts object keys recursively. Doesn't rely on serde_json's map ordering, which changes with its
+/// `preserve_order` feature.
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sorted(v))).collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        v => v,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::{SignerData, TxMetadata};
+    use cosmrs::tx;
+
+    fn msg_send() -> Any {
+        Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: MsgSend {
+                from_address: "cosmos1from".to_string(),
+                to_address: "cosmos1to".to_string(),
+                amount: vec![Coin {
+                    amount: "10".to_string(),
+                    denom: "uatom".to_string(),
+                }],
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn formats_rfc3339() {
+        let timestamp = |seconds, nanos| Timestamp { seconds, nanos };
+
+        assert_eq!(rfc3339(&timestamp(0, 0)), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(&timestamp(951782400, 0)), "2000-02-29T00:00:00Z");
+        assert_eq!(
+            rfc3339(&timestamp(1000000000, 500000000)),
+            "2001-09-09T01:46:40.5Z"
+        );
+        assert_eq!(
+            rfc3339(&timestamp(1700000000, 123)),
+            "2023-11-14T22:13:20.000000123Z"
+        );
+    }
+
+    #[test]
+    fn encodes_msgs() {
+        let exec = Any {
+            type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+            value: MsgExec {
+                grantee: "cosmos1grantee".to_string(),
+                msgs: vec![msg_send()],
+            }
+            .encode_to_vec(),
+        };
+        let grant = Any {
+            type_url: "/cosmos.authz.v1beta1.MsgGrant".to_string(),
+            value: MsgGrant {
+                granter: "cosmos1granter".to_string(),
+                grantee: "cosmos1grantee".to_string(),
+                grant: Some(Grant {
+                    authorization: Some(Any {
+                        type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
+                        value: GenericAuthorization {
+                            msg: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                        }
+                        .encode_to_vec(),
+                    }),
+                    expiration: None,
+                }),
+            }
+            .encode_to_vec(),
+        };
+
+        assert_eq!(
+            msg_to_json(&exec).unwrap(),
+            json!({
+                "type": "cosmos-sdk/MsgExec",
+                "value": {
+                    "grantee": "cosmos1grantee",
+                    "msgs": [{
+                        "type": "cosmos-sdk/MsgSend",
+                        "value": {
+                            "amount": [{ "amount": "10", "denom": "uatom" }],
+                            "from_address": "cosmos1from",
+                            "to_address": "cosmos1to",
+                        },
+                    }],
+                },
+            })
+        );
+        // the empty expiration is omitted
+        assert_eq!(
+            msg_to_json(&grant).unwrap()["value"]["grant"],
+            json!({
+                "authorization": {
+                    "type": "cosmos-sdk/GenericAuthorization",
+                    "value": { "msg": "/cosmos.bank.v1beta1.MsgSend" },
+                },
+            })
+        );
+        let vote = Any {
+            type_url: "/cosmos.gov.v1beta1.MsgVoteWeighted".to_string(),
+            value: MsgVoteWeighted {
+                proposal_id: 12,
+                voter: "cosmos1voter".to_string(),
+                options: vec![WeightedVoteOption {
+                    option: 1,
+                    weight: "500000000000000000".to_string(),
+                }],
+            }
+            .encode_to_vec(),
+        };
+
+        assert_eq!(
+            msg_to_json(&vote).unwrap()["value"],
+            json!({
+                "proposal_id": "12",
+                "voter": "cosmos1voter",
+                "options": [{ "option": 1, "weight": "0.500000000000000000" }],
+            })
+        );
+        assert!(msg_to_json(&Any {
+            type_url: "/cosmos.staking.v1beta1.MsgCreateValidator".to_string(),
+            value: vec![],
+        })
+        .is_err());
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    #[test]
+    fn keeps_empty_contract_msg_fields() {
+        let execute = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+            value: MsgExecuteContract {
+                sender: "juno1sender".to_string(),
+                contract: "juno1contract".to_string(),
+                msg: br#"{"set_memo":{"memo":"","tags":[]}}"#.to_vec(),
+                funds: vec![],
+            }
+            .encode_to_vec(),
+        };
+
+        assert_eq!(
+            msg_to_json(&execute).unwrap(),
+            json!({
+                "type": "wasm/MsgExecuteContract",
+                "value": {
+                    "sender": "juno1sender",
+                    "contract": "juno1contract",
+                    "msg": { "set_memo": { "memo": "", "tags": [] } },
+                },
+            })
+        );
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    #[test]
+    fn keeps_empty_contract_msg_fields_in_exec() {
+        let execute = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+            value: MsgExecuteContract {
+                sender: "juno1granter".to_string(),
+                contract: "juno1contract".to_string(),
+                msg: br#"{"transfer":{"memo":""}}"#.to_vec(),
+                funds: vec![],
+            }
+            .encode_to_vec(),
+        };
+        let exec = Any {
+            type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+            value: MsgExec {
+                grantee: "juno1grantee".to_string(),
+                msgs: vec![execute],
+            }
+            .encode_to_vec(),
+        };
+
+        assert_eq!(
+            msg_to_json(&exec).unwrap(),
+            json!({
+                "type": "cosmos-sdk/MsgExec",
+                "value": {
+                    "grantee": "juno1grantee",
+                    "msgs": [{
+                        "type": "wasm/MsgExecuteContract",
+                        "value": {
+                            "sender": "juno1granter",
+                            "contract": "juno1contract",
+                            "msg": { "transfer": { "memo": "" } },
+                        },
+                    }],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn builds_sorted_sign_doc() {
+        let tx_body = tx::Body::new(vec![msg_send()], "<memo>", 0u32);
+        let signer = crate::account::AccountInfo::new("");
+        let signer_data = SignerData {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 7,
+            sequence: 2,
+        };
+        let tx_metadata = TxMetadata {
+            fee: vec![crate::tx::Coin {
+                amount: 500,
+                denom: "uatom".to_string(),
+            }],
+            gas_limit: 200000,
+            sign_mode: crate::tx::SignMode::LegacyAminoJson,
+            ..Default::default()
+        };
+        let unsigned =
+            UnsignedTx::new(&tx_body, signer.public_key(), &signer_data, &tx_metadata).unwrap();
+
+        assert_eq!(
+            String::from_utf8(sign_doc_bytes(&unsigned).unwrap()).unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"cosmoshub-4","#,
+                r#""fee":{"amount":[{"amount":"500","denom":"uatom"}],"gas":"200000"},"#,
+                r#""memo":"\u003cmemo\u003e","#,
+                r#""msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"10","denom":"uatom"}],"#,
+                r#""from_address":"cosmos1from","to_address":"cosmos1to"}}],"sequence":"2"}"#
+            )
+        );
+        assert_eq!(
+            unsigned.sign_mode().unwrap(),
+            crate::tx::SignMode::LegacyAminoJson
+        );
+        assert_eq!(
+            unsigned.sign_bytes().unwrap(),
+            sign_doc_bytes(&unsigned).unwrap()
+        );
+    }
+}
@@ -0,0 +1,416 @@
+//! Types for building, signing and broadcasting txs as separate steps. Building and signing require no network
+//! connection, so a tx can be built on an online machine, signed on an air-gapped one, and broadcast later with
+//! [`crate::chain::client::ChainClient::broadcast_signed_tx`].
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+    mode_info::{self, Sum},
+    AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxRaw,
+};
+use cosmrs::tx;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::{AccountInfo, PublicKey},
+    error::TxError,
+    utils::hex_bytes,
+};
+
+use super::{amino, Any, MultisigAccount, PartialSignature, SignMode, TxMetadata};
+
+/// Chain specific data a signer commits to. Must be known ahead of time when building a tx offline.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SignerData {
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+/// Collects any number of messages into a single tx. Can be built into an [`UnsignedTx`] without any network
+/// access, or signed and sent in one step with [`crate::chain::client::ChainClient::sign_and_send_tx`].
+#[derive(Clone, Debug, Default)]
+pub struct TxBuilder {
+    msgs: Vec<Any>,
+    signer_data: SignerData,
+    tx_metadata: Option<TxMetadata>,
+}
+
+impl TxBuilder {
+    pub fn new(signer_data: SignerData) -> TxBuilder {
+        TxBuilder {
+            signer_data,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_msg(mut self, msg: Any) -> TxBuilder {
+        self.msgs.push(msg);
+        self
+    }
+
+    pub fn with_msgs(mut self, msgs: impl IntoIterator<Item = Any>) -> TxBuilder {
+        self.msgs.extend(msgs);
+        self
+    }
+
+    pub fn with_signer_data(mut self, signer_data: SignerData) -> TxBuilder {
+        self.signer_data = signer_data;
+        self
+    }
+
+    pub fn with_tx_metadata(mut self, tx_metadata: TxMetadata) -> TxBuilder {
+        self.tx_metadata = Some(tx_metadata);
+        self
+    }
+
+    pub fn msgs(&self) -> &[Any] {
+        &self.msgs
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<Any>, Option<TxMetadata>) {
+        (self.msgs, self.tx_metadata)
+    }
+
+    /// Builds the unsigned tx. The gas limit in the tx metadata is used as-is, so it must be set explicitly.
+    pub fn build(self, signer_public_key: PublicKey) -> Result<UnsignedTx, TxError> {
+        if self.msgs.is_empty() {
+            return Err(TxError::Serialization(
+                "a tx must contain at least one message".to_string(),
+            ));
+        }
+
+        let tx_metadata = self.tx_metadata.unwrap_or_default();
+        let tx_body = tx::Body::new(self.msgs, &tx_metadata.memo, tx_metadata.timeout_height);
+
+        UnsignedTx::new(&tx_body, signer_public_key, &self.signer_data, &tx_metadata)
+    }
+
+    /// Builds the unsigned tx for a multisig account. `signers` are the members that will sign and must be decided
+    /// up front, since [`SignMode::Direct`] commits to the multisig's signer info. See [`MultisigAccount`].
+    pub fn build_multisig(
+        self,
+        multisig: &MultisigAccount,
+        signers: &[PublicKey],
+    ) -> Result<UnsignedTx, TxError> {
+        if self.msgs.is_empty() {
+            return Err(TxError::Serialization(
+                "a tx must contain at least one message".to_string(),
+            ));
+        }
+
+        let tx_metadata = self.tx_metadata.unwrap_or_default();
+        let tx_body = tx::Body::new(self.msgs, &tx_metadata.memo, tx_metadata.timeout_height);
+
+        multisig.unsigned_tx(&tx_body, signers, &self.signer_data, &tx_metadata)
+    }
+}
+
+/// A SIGN_MODE_DIRECT sign doc for a single signer. Serializes to JSON with hex encoded bytes, or to the
+/// protobuf encoding of `cosmos.tx.v1beta1.SignDoc`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct UnsignedTx {
+    #[serde(with = "hex_bytes")]
+    pub body_bytes: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: String,
+    pub account_number: u64,
+}
+
+impl UnsignedTx {
+    pub fn new(
+        tx_body: &tx::Body,
+        signer_public_key: PublicKey,
+        signer_data: &SignerData,
+        tx_metadata: &TxMetadata,
+    ) -> Result<UnsignedTx, TxError> {
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(signer_public_key.into()),
+                mode_info: Some(single_mode_info(tx_metadata.sign_mode)),
+                sequence: signer_data.sequence,
+            }],
+            fee: Some(fee(tx_metadata)),
+        };
+
+        UnsignedTx::from_auth_info_bytes(tx_body, auth_info.encode_to_vec(), signer_data)
+    }
+
+    pub(crate) fn from_auth_info_bytes(
+        tx_body: &tx::Body,
+        auth_info_bytes: Vec<u8>,
+        signer_data: &SignerData,
+    ) -> Result<UnsignedTx, TxError> {
+        Ok(UnsignedTx {
+            body_bytes: tx_body
+                .clone()
+                .into_bytes()
+                .map_err(|e| TxError::Serialization(e.to_string()))?,
+            auth_info_bytes,
+            chain_id: signer_data.chain_id.clone(),
+            account_number: signer_data.account_number,
+        })
+    }
+
+    pub fn from_json(json: &str) -> Result<UnsignedTx, TxError> {
+        serde_json::from_str(json).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, TxError> {
+        serde_json::to_string(self).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<UnsignedTx, TxError> {
+        let sign_doc = SignDoc::decode(bytes).map_err(|e| TxError::Serialization(e.to_string()))?;
+
+        Ok(UnsignedTx {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc.account_number,
+        })
+    }
+
+    /// The protobuf encoded sign doc
+    pub fn to_bytes(&self) -> Vec<u8> {
+        SignDoc {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        }
+        .encode_to_vec()
+    }
+
+    /// The sign mode the tx was built with. For a multisig tx, this is the sign mode of its members.
+    pub fn sign_mode(&self) -> Result<SignMode, TxError> {
+        let auth_info = AuthInfo::decode(&self.auth_info_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let mode_info = auth_info
+            .signer_infos
+            .into_iter()
+            .next()
+            .and_then(|s| s.mode_info)
+            .and_then(|m| m.sum);
+
+        match mode_info {
+            Some(Sum::Single(single)) => SignMode::try_from(single.mode),
+            Some(Sum::Multi(multi)) => {
+                match multi.mode_infos.into_iter().next().and_then(|m| m.sum) {
+                    Some(Sum::Single(single)) => SignMode::try_from(single.mode),
+                    _ => Err(TxError::Serialization(
+                        "nested multisigs are not supported".to_string(),
+                    )),
+                }
+            }
+            None => Err(TxError::Serialization(
+                "tx has no signer mode info".to_string(),
+            )),
+        }
+    }
+
+    /// The bytes that get signed. For [`SignMode::Direct`] these are the same as [`UnsignedTx::to_bytes`], for
+    /// [`SignMode::LegacyAminoJson`] they are the sorted amino JSON sign doc.
+    pub fn sign_bytes(&self) -> Result<Vec<u8>, TxError> {
+        match self.sign_mode()? {
+            SignMode::Direct => Ok(self.to_bytes()),
+            SignMode::LegacyAminoJson => amino::sign_doc_bytes(self),
+        }
+    }
+
+    /// Signs the tx. Does not require a network connection.
+    pub fn sign(&self, signer: &AccountInfo) -> Result<SignedTx, TxError> {
+        let signature = signer
+            .private_key()
+            .sign(&self.sign_bytes()?)
+            .map_err(|e| TxError::Signing(e.to_string()))?;
+
+        Ok(SignedTx {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            signatures: vec![signature.as_ref().to_vec()],
+        })
+    }
+
+    /// Signs the tx as one member of a multisig. The resulting [`PartialSignature`]s are combined with
+    /// [`MultisigAccount::combine`]. Does not require a network connection.
+    pub fn sign_partial(&self, signer: &AccountInfo) -> Result<PartialSignature, TxError> {
+        let signature = signer
+            .private_key()
+            .sign(&self.sign_bytes()?)
+            .map_err(|e| TxError::Signing(e.to_string()))?;
+
+        Ok(PartialSignature {
+            public_key: signer.public_key().to_bytes(),
+            signature: signature.as_ref().to_vec(),
+        })
+    }
+}
+
+pub(crate) fn single_mode_info(sign_mode: SignMode) -> ModeInfo {
+    ModeInfo {
+        sum: Some(Sum::Single(mode_info::Single {
+            mode: sign_mode.into(),
+        })),
+    }
+}
+
+/// Built from the protobuf type, whose string amounts hold fees in any u128 amount
+pub(crate) fn fee(tx_metadata: &TxMetadata) -> Fee {
+    Fee {
+        amount: tx_metadata.fee.iter().cloned().map(Into::into).collect(),
+        gas_limit: tx_metadata.gas_limit,
+        payer: tx_metadata
+            .fee_payer
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        granter: tx_metadata
+            .fee_granter
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+    }
+}
+
+/// A signed tx ready to be broadcast. Serializes to JSON with hex encoded bytes, or to the protobuf
+/// encoding of `cosmos.tx.v1beta1.TxRaw`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SignedTx {
+    #[serde(with = "hex_bytes")]
+    pub body_bytes: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub auth_info_bytes: Vec<u8>,
+    #[serde(with = "hex_bytes::vec")]
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl SignedTx {
+    pub fn from_json(json: &str) -> Result<SignedTx, TxError> {
+        serde_json::from_str(json).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, TxError> {
+        serde_json::to_string(self).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignedTx, TxError> {
+        let tx_raw = TxRaw::decode(bytes).map_err(|e| TxError::Serialization(e.to_string()))?;
+
+        Ok(SignedTx {
+            body_bytes: tx_raw.body_bytes,
+            auth_info_bytes: tx_raw.auth_info_bytes,
+            signatures: tx_raw.signatures,
+        })
+    }
+
+    /// The protobuf encoded tx. These are the bytes that get broadcast.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        TxRaw {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            signatures: self.signatures.clone(),
+        }
+        .encode_to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::Coin;
+    use cosmrs::{bank::MsgSend, tx::Msg};
+
+    fn msg_send(signer: &AccountInfo) -> Any {
+        MsgSend {
+            from_address: signer.id("cosmos").unwrap(),
+            to_address: AccountInfo::new("").id("cosmos").unwrap(),
+            amount: vec![cosmrs::Coin::try_from(Coin {
+                amount: 1,
+                denom: "uatom".to_string(),
+            })
+            .unwrap()],
+        }
+        .to_any()
+        .unwrap()
+    }
+
+    fn unsigned_tx(signer: &AccountInfo) -> UnsignedTx {
+        let msg = msg_send(signer);
+        let signer_data = SignerData {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 12,
+            sequence: 3,
+        };
+        let tx_metadata = TxMetadata {
+            fee: vec![Coin {
+                amount: 0,
+                denom: "uatom".to_string(),
+            }],
+            gas_limit: 100000,
+            memo: "offline".to_string(),
+            ..Default::default()
+        };
+
+        TxBuilder::new(signer_data)
+            .with_msg(msg)
+            .with_tx_metadata(tx_metadata)
+            .build(signer.public_key())
+            .unwrap()
+    }
+
+    #[test]
+    fn builds_and_signs_offline() {
+        let signer = AccountInfo::new("");
+        let unsigned = unsigned_tx(&signer);
+
+        assert_eq!(unsigned.sign_mode().unwrap(), SignMode::Direct);
+        assert_eq!(unsigned.sign_bytes().unwrap(), unsigned.to_bytes());
+
+        let json = unsigned.to_json().unwrap();
+        assert_eq!(UnsignedTx::from_json(&json).unwrap(), unsigned);
+        assert_eq!(
+            UnsignedTx::from_bytes(&unsigned.to_bytes()).unwrap(),
+            unsigned
+        );
+
+        let signed = unsigned.sign(&signer).unwrap();
+        assert_eq!(signed.signatures.len(), 1);
+        assert_eq!(signed.signatures[0].len(), 64);
+        assert_eq!(signed.body_bytes, unsigned.body_bytes);
+
+        let json = signed.to_json().unwrap();
+        assert_eq!(SignedTx::from_json(&json).unwrap(), signed);
+        assert_eq!(SignedTx::from_bytes(&signed.to_bytes()).unwrap(), signed);
+    }
+
+    #[test]
+    fn builds_multi_msg_tx() {
+        let signer = AccountInfo::new("");
+        let msg = msg_send(&signer);
+        let unsigned = TxBuilder::new(SignerData::default())
+            .with_msg(msg.clone())
+            .with_msgs(vec![msg.clone(), msg])
+            .with_tx_metadata(TxMetadata {
+                fee: vec![Coin {
+                    amount: 0,
+                    denom: "uatom".to_string(),
+                }],
+                ..Default::default()
+            })
+            .build(signer.public_key())
+            .unwrap();
+        let body =
+            cosmrs::proto::cosmos::tx::v1beta1::TxBody::decode(&unsigned.body_bytes[..]).unwrap();
+
+        assert_eq!(body.messages.len(), 3);
+    }
+
+    #[test]
+    fn build_requires_msgs() {
+        let signer = AccountInfo::new("");
+
+        assert!(TxBuilder::new(SignerData::default())
+            .build(signer.public_key())
+            .is_err());
+    }
+}
@@ -0,0 +1,648 @@
+//! Coin types with amounts large enough for 18 decimal tokens, and fixed point decimal coins for rewards and
+//! gas prices.
+use cosmos_sdk_proto::cosmos::base::v1beta1::{Coin as ProtoCoin, DecCoin as ProtoDecCoin};
+use cosmrs::Denom;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::error::TxError;
+
+/// A coin with an integer amount. Amounts serialize as strings, but integers are also accepted when deserializing.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Hash, Serialize)]
+pub struct Coin {
+    #[serde(with = "amount")]
+    pub amount: u128,
+    pub denom: String,
+}
+
+impl Coin {
+    pub fn new(amount: u128, denom: &str) -> Coin {
+        Coin {
+            amount,
+            denom: denom.to_string(),
+        }
+    }
+
+    /// Returns `None` if the denoms differ or the sum overflows
+    pub fn checked_add(&self, other: &Coin) -> Option<Coin> {
+        if self.denom != other.denom {
+            return None;
+        }
+
+        Some(Coin::new(
+            self.amount.checked_add(other.amount)?,
+            &self.denom,
+        ))
+    }
+
+    /// Returns `None` if the denoms differ or `other` is larger
+    pub fn checked_sub(&self, other: &Coin) -> Option<Coin> {
+        if self.denom != other.denom {
+            return None;
+        }
+
+        Some(Coin::new(
+            self.amount.checked_sub(other.amount)?,
+            &self.denom,
+        ))
+    }
+
+    pub fn checked_mul(&self, factor: u128) -> Option<Coin> {
+        Some(Coin::new(self.amount.checked_mul(factor)?, &self.denom))
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// Parses coins in the `100uatom` format
+impl FromStr for Coin {
+    type Err = TxError;
+
+    fn from_str(s: &str) -> Result<Coin, Self::Err> {
+        let (amount, denom) = split_amount_and_denom(s)?;
+
+        Ok(Coin {
+            amount: amount.parse()?,
+            denom,
+        })
+    }
+}
+
+impl From<Coin> for ProtoCoin {
+    fn from(coin: Coin) -> Self {
+        ProtoCoin {
+            amount: coin.amount.to_string(),
+            denom: coin.denom,
+        }
+    }
+}
+
+impl TryFrom<Coin> for cosmrs::Coin {
+    type Error = TxError;
+
+    fn try_from(coin: Coin) -> Result<cosmrs::Coin, Self::Error> {
+        cosmrs::Coin::try_from(&coin)
+    }
+}
+
+impl TryFrom<&Coin> for cosmrs::Coin {
+    type Error = TxError;
+
+    /// Goes through the proto string representation so amounts cosmrs can't represent result in an error instead
+    /// of being truncated.
+    fn try_from(coin: &Coin) -> Result<cosmrs::Coin, Self::Error> {
+        Ok(cosmrs::Coin::try_from(ProtoCoin::from(coin.clone()))?)
+    }
+}
+
+impl TryFrom<ProtoCoin> for Coin {
+    type Error = TxError;
+
+    fn try_from(coin: ProtoCoin) -> Result<Coin, Self::Error> {
+        Coin::try_from(&coin)
+    }
+}
+
+impl TryFrom<&ProtoCoin> for Coin {
+    type Error = TxError;
+
+    fn try_from(coin: &ProtoCoin) -> Result<Coin, Self::Error> {
+        Ok(Coin {
+            denom: coin.denom.clone(),
+            amount: coin.amount.parse()?,
+        })
+    }
+}
+
+/// A non-negative fixed point decimal with 18 decimal places, the same precision as the Cosmos SDK's `sdk.Dec`. The
+/// integer part is a `u128` like [`Coin`] amounts, so large amounts of 18 decimal tokens can be represented.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dec {
+    integer: u128,
+    /// Scaled by 10^18, always less than 10^18
+    fraction: u64,
+}
+
+impl Dec {
+    pub const DECIMALS: u32 = 18;
+    pub const MAX: Dec = Dec {
+        integer: u128::MAX,
+        fraction: Dec::SCALE - 1,
+    };
+    const SCALE: u64 = 10u64.pow(Dec::DECIMALS);
+
+    /// Creates a decimal from its value scaled by 10^18
+    pub fn from_atomics(atomics: u128) -> Dec {
+        Dec {
+            integer: atomics / u128::from(Dec::SCALE),
+            fraction: (atomics % u128::from(Dec::SCALE)) as u64,
+        }
+    }
+
+    /// Parses a decimal from its value scaled by 10^18 without a decimal point, which is how the SDK encodes
+    /// decimals in protobuf
+    pub fn from_atomics_str(atomics: &str) -> Result<Dec, TxError> {
+        let invalid = || TxError::TypeConversion(format!("invalid decimal atomics {}", atomics));
+
+        if atomics.is_empty() || !atomics.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let split = atomics.len().saturating_sub(Dec::DECIMALS as usize);
+        let (integer, fraction) = atomics.split_at(split);
+
+        Ok(Dec {
+            integer: if integer.is_empty() {
+                0
+            } else {
+                integer.parse().map_err(|_| invalid())?
+            },
+            fraction: fraction.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// The value scaled by 10^18 without a decimal point, as encoded in protobuf
+    pub fn to_atomics_string(&self) -> String {
+        if self.integer == 0 {
+            self.fraction.to_string()
+        } else {
+            format!("{}{:018}", self.integer, self.fraction)
+        }
+    }
+
+    pub fn from_integer(integer: u128) -> Dec {
+        Dec {
+            integer,
+            fraction: 0,
+        }
+    }
+
+    pub fn checked_add(&self, other: Dec) -> Option<Dec> {
+        let fraction = self.fraction + other.fraction;
+        let carry = u128::from(fraction >= Dec::SCALE);
+
+        Some(Dec {
+            integer: self
+                .integer
+                .checked_add(other.integer)?
+                .checked_add(carry)?,
+            fraction: fraction % Dec::SCALE,
+        })
+    }
+
+    pub fn checked_sub(&self, other: Dec) -> Option<Dec> {
+        let borrow = u128::from(self.fraction < other.fraction);
+
+        Some(Dec {
+            integer: self
+                .integer
+                .checked_sub(other.integer)?
+                .checked_sub(borrow)?,
+            fraction: (self.fraction + Dec::SCALE - other.fraction) % Dec::SCALE,
+        })
+    }
+
+    /// Multiplies by an integer, e.g. a gas price by a gas limit
+    pub fn checked_mul_integer(&self, factor: u128) -> Option<Dec> {
+        let scale = u128::from(Dec::SCALE);
+        let fraction = u128::from(self.fraction);
+        // Splitting the factor keeps the fraction's product below 10^36
+        let (high, low) = (factor / scale, factor % scale);
+        let fraction_product = fraction * low;
+
+        Some(Dec {
+            integer: self
+                .integer
+                .checked_mul(factor)?
+                .checked_add(fraction * high)?
+                .checked_add(fraction_product / scale)?,
+            fraction: (fraction_product % scale) as u64,
+        })
+    }
+
+    /// The integer part, discarding any fraction
+    pub fn truncate(&self) -> u128 {
+        self.integer
+    }
+
+    /// The fractional part scaled by 10^18
+    pub fn fraction(&self) -> u64 {
+        self.fraction
+    }
+
+    /// The smallest integer greater than or equal to the decimal. `None` if it overflows.
+    pub fn checked_ceil(&self) -> Option<u128> {
+        self.integer.checked_add(u128::from(self.fraction != 0))
+    }
+}
+
+impl fmt::Display for Dec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fraction = format!("{:018}", self.fraction);
+        let fraction = fraction.trim_end_matches('0');
+
+        if fraction.is_empty() {
+            write!(f, "{}", self.integer)
+        } else {
+            write!(f, "{}.{}", self.integer, fraction)
+        }
+    }
+}
+
+impl FromStr for Dec {
+    type Err = TxError;
+
+    fn from_str(s: &str) -> Result<Dec, Self::Err> {
+        let invalid = || TxError::TypeConversion(format!("invalid decimal {}", s));
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+        if integer.is_empty()
+            || fraction.len() > Dec::DECIMALS as usize
+            || !s.chars().all(|c| c.is_ascii_digit() || c == '.')
+        {
+            return Err(invalid());
+        }
+
+        Ok(Dec {
+            integer: integer.parse().map_err(|_| invalid())?,
+            fraction: format!("{:0<18}", fraction)
+                .parse()
+                .map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Serialize for Dec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Dec, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A coin with a decimal amount, used for distribution rewards and gas prices
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Hash, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DecCoin {
+    pub amount: Dec,
+    pub denom: String,
+}
+
+/// A price per unit of gas such as `0.025uatom`
+pub type GasPrice = DecCoin;
+
+impl DecCoin {
+    pub fn new(amount: Dec, denom: &str) -> DecCoin {
+        DecCoin {
+            amount,
+            denom: denom.to_string(),
+        }
+    }
+
+    /// Returns `None` if the denoms differ or the sum overflows
+    pub fn checked_add(&self, other: &DecCoin) -> Option<DecCoin> {
+        if self.denom != other.denom {
+            return None;
+        }
+
+        Some(DecCoin::new(
+            self.amount.checked_add(other.amount)?,
+            &self.denom,
+        ))
+    }
+
+    /// The integer part of the amount as a [`Coin`]. Rewards can only be withdrawn in whole units.
+    pub fn truncate(&self) -> Coin {
+        Coin::new(self.amount.truncate(), &self.denom)
+    }
+
+    /// When used as a gas price, the fee for `gas_limit` units of gas rounded up. `None` if it overflows.
+    pub fn fee(&self, gas_limit: u64) -> Option<Coin> {
+        let amount = self
+            .amount
+            .checked_mul_integer(gas_limit.into())?
+            .checked_ceil()?;
+
+        Some(Coin::new(amount, &self.denom))
+    }
+}
+
+impl fmt::Display for DecCoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// Parses decimal coins in the `0.025uatom` format
+impl FromStr for DecCoin {
+    type Err = TxError;
+
+    fn from_str(s: &str) -> Result<DecCoin, Self::Err> {
+        let (amount, denom) = split_amount_and_denom(s)?;
+
+        Ok(DecCoin {
+            amount: amount.parse()?,
+            denom,
+        })
+    }
+}
+
+impl TryFrom<String> for DecCoin {
+    type Error = TxError;
+
+    fn try_from(s: String) -> Result<DecCoin, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<DecCoin> for String {
+    fn from(coin: DecCoin) -> Self {
+        coin.to_string()
+    }
+}
+
+impl From<DecCoin> for ProtoDecCoin {
+    fn from(coin: DecCoin) -> Self {
+        ProtoDecCoin {
+            amount: coin.amount.to_atomics_string(),
+            denom: coin.denom,
+        }
+    }
+}
+
+impl TryFrom<ProtoDecCoin> for DecCoin {
+    type Error = TxError;
+
+    fn try_from(coin: ProtoDecCoin) -> Result<DecCoin, Self::Error> {
+        DecCoin::try_from(&coin)
+    }
+}
+
+impl TryFrom<&ProtoDecCoin> for DecCoin {
+    type Error = TxError;
+
+    /// Protobuf encodes the amount as its value scaled by 10^18, without a decimal point
+    fn try_from(coin: &ProtoDecCoin) -> Result<DecCoin, Self::Error> {
+        Ok(DecCoin {
+            amount: Dec::from_atomics_str(&coin.amount)?,
+            denom: coin.denom.clone(),
+        })
+    }
+}
+
+/// Computes the fee for `gas_limit` in each of the `gas_prices` denoms, like the `--gas-prices` flag of Cosmos SDK
+/// CLIs. Errors if there are no gas prices rather than returning an empty fee.
+pub fn calculate_fee(gas_limit: u64, gas_prices: &[GasPrice]) -> Result<Vec<Coin>, TxError> {
+    if gas_prices.is_empty() {
+        return Err(TxError::MissingFee(
+            "no gas prices configured, set gas_prices or an explicit fee".to_string(),
+        ));
+    }
+
+    gas_prices
+        .iter()
+        .map(|p| {
+            p.fee(gas_limit).ok_or_else(|| {
+                TxError::TypeConversion(format!("fee for {} gas at {} overflows", gas_limit, p))
+            })
+        })
+        .collect()
+}
+
+fn split_amount_and_denom(s: &str) -> Result<(&str, String), TxError> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| TxError::TypeConversion(format!("{} has no denom", s)))?;
+    let (amount, denom) = s.split_at(split);
+
+    Ok((amount, denom.parse::<Denom>()?.to_string()))
+}
+
+/// Serializes amounts as strings like the Cosmos SDK, and deserializes strings or integers
+pub(crate) mod amount {
+    use serde::{de, Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(amount: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(amount)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        deserializer.deserialize_any(AmountVisitor)
+    }
+
+    struct AmountVisitor;
+
+    impl<'de> de::Visitor<'de> for AmountVisitor {
+        type Value = u128;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an integer amount or a string containing one")
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<u128, E> {
+            Ok(v.into())
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<u128, E> {
+            u128::try_from(v).map_err(E::custom)
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<u128, E> {
+            Ok(v)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<u128, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_coin() {
+        let coin = Coin {
+            amount: 100,
+            denom: "utest".to_string(),
+        };
+
+        cosmrs::Coin::try_from(&coin).unwrap();
+        cosmrs::Coin::try_from(coin).unwrap();
+    }
+
+    #[test]
+    fn handles_18_decimal_amounts() {
+        // 1 billion aevmos
+        let supply = ProtoCoin {
+            amount: "1000000000000000000000000000".to_string(),
+            denom: "aevmos".to_string(),
+        };
+        let coin = Coin::try_from(&supply).unwrap();
+
+        assert_eq!(coin.amount, 10u128.pow(27));
+        assert_eq!(ProtoCoin::from(coin.clone()), supply);
+        assert_eq!(coin.to_string(), "1000000000000000000000000000aevmos");
+        assert_eq!(
+            "1000000000000000000000000000aevmos"
+                .parse::<Coin>()
+                .unwrap(),
+            coin
+        );
+
+        let json = serde_json::to_string(&coin).unwrap();
+        assert_eq!(
+            json,
+            r#"{"amount":"1000000000000000000000000000","denom":"aevmos"}"#
+        );
+        assert_eq!(serde_json::from_str::<Coin>(&json).unwrap(), coin);
+        assert_eq!(
+            serde_json::from_str::<Coin>(r#"{"amount":5,"denom":"uatom"}"#)
+                .unwrap()
+                .amount,
+            5
+        );
+    }
+
+    #[test]
+    fn checked_coin_arithmetic() {
+        let a = Coin::new(10, "uatom");
+        let b = Coin::new(3, "uatom");
+
+        assert_eq!(a.checked_add(&b).unwrap().amount, 13);
+        assert_eq!(a.checked_sub(&b).unwrap().amount, 7);
+        assert!(b.checked_sub(&a).is_none());
+        assert!(a.checked_add(&Coin::new(1, "uosmo")).is_none());
+        assert!(Coin::new(u128::MAX, "uatom").checked_add(&b).is_none());
+        assert!(a.checked_mul(u128::MAX).is_none());
+    }
+
+    #[test]
+    fn parses_dec_coins() {
+        let gas_price: GasPrice = "0.025uatom".parse().unwrap();
+
+        assert_eq!(gas_price.denom, "uatom");
+        assert_eq!(gas_price.to_string(), "0.025uatom");
+        assert_eq!(gas_price.fee(200000).unwrap().amount, 5000);
+        assert_eq!(gas_price.fee(200001).unwrap().amount, 5001);
+        assert_eq!(
+            "1ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                .parse::<GasPrice>()
+                .unwrap()
+                .fee(10)
+                .unwrap()
+                .amount,
+            10
+        );
+        assert_eq!(
+            "0uatom"
+                .parse::<GasPrice>()
+                .unwrap()
+                .fee(200000)
+                .unwrap()
+                .amount,
+            0
+        );
+
+        assert!("uatom".parse::<GasPrice>().is_err());
+        assert!("0.025".parse::<GasPrice>().is_err());
+        assert!(".5uatom".parse::<GasPrice>().is_err());
+        assert!("1.2.3uatom".parse::<GasPrice>().is_err());
+        assert!("0.0000000000000000001uatom".parse::<GasPrice>().is_err());
+
+        let fee = calculate_fee(
+            100000,
+            &["0.025uatom".parse().unwrap(), "0.1uosmo".parse().unwrap()],
+        )
+        .unwrap();
+        assert_eq!(fee.len(), 2);
+        assert_eq!(fee[1].amount, 10000);
+        assert_eq!(fee[1].denom, "uosmo");
+        assert!(matches!(
+            calculate_fee(100000, &[]),
+            Err(TxError::MissingFee(_))
+        ));
+
+        let huge_price = DecCoin::new(Dec::from_integer(u128::MAX / 2), "aevmos");
+
+        assert!(huge_price.fee(3).is_none());
+        assert!(matches!(
+            calculate_fee(3, &[huge_price]),
+            Err(TxError::TypeConversion(_))
+        ));
+    }
+
+    #[test]
+    fn converts_proto_dec_coins() {
+        // 1.5uatom of rewards
+        let rewards = ProtoDecCoin {
+            amount: "1500000000000000000".to_string(),
+            denom: "uatom".to_string(),
+        };
+        let dec_coin = DecCoin::try_from(&rewards).unwrap();
+
+        assert_eq!(dec_coin.to_string(), "1.5uatom");
+        assert_eq!(dec_coin.truncate(), Coin::new(1, "uatom"));
+        assert_eq!(dec_coin.amount.checked_ceil(), Some(2));
+        assert_eq!(ProtoDecCoin::from(dec_coin.clone()), rewards);
+        assert_eq!(
+            dec_coin
+                .checked_add(&"0.5uatom".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "2uatom"
+        );
+    }
+
+    #[test]
+    fn handles_large_decs() {
+        // 123456789012 EVMOS of commission, more than 10^20 base units
+        let commission = ProtoDecCoin {
+            amount: "123456789012000000000000000000500000000000000000".to_string(),
+            denom: "aevmos".to_string(),
+        };
+        let dec_coin = DecCoin::try_from(&commission).unwrap();
+
+        assert_eq!(
+            dec_coin.to_string(),
+            "123456789012000000000000000000.5aevmos"
+        );
+        assert_eq!(dec_coin.truncate().amount, 123456789012 * 10u128.pow(18));
+        assert_eq!(ProtoDecCoin::from(dec_coin.clone()), commission);
+        assert_eq!(dec_coin.to_string().parse::<DecCoin>().unwrap(), dec_coin);
+        assert_eq!(
+            Dec::from_atomics_str("5").unwrap().to_string(),
+            "0.000000000000000005"
+        );
+        assert_eq!(Dec::from_atomics_str("5").unwrap().to_atomics_string(), "5");
+        assert!(Dec::from_atomics_str("").is_err());
+        assert!(Dec::from_atomics_str("1.5").is_err());
+
+        let a: Dec = "0.7".parse().unwrap();
+        let b: Dec = "1.4".parse().unwrap();
+
+        assert_eq!(a.checked_add(b).unwrap().to_string(), "2.1");
+        assert_eq!(b.checked_sub(a).unwrap(), a);
+        assert!(a.checked_sub(b).is_none());
+        assert_eq!(
+            a.checked_mul_integer(10u128.pow(30) + 3)
+                .unwrap()
+                .to_string(),
+            "700000000000000000000000000002.1"
+        );
+        assert!(Dec::MAX.checked_add(a).is_none());
+        assert!(Dec::MAX.checked_ceil().is_none());
+    }
+}
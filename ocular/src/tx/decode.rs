@@ -0,0 +1,899 @@
+//! Decodes raw tx bytes into a readable view. Messages of every module in [`crate::cosmos_modules`] and IBC
+//! transfers are decoded into their concrete types, while messages with unknown type URLs are kept as raw bytes and shown as hex.
+//!
+//! The JSON output follows the Cosmos SDK's protobuf JSON: snake_case field names, `@type` for `Any`s, 64 bit
+//! integers as strings and timestamps as RFC 3339.
+use cosmos_sdk_proto::cosmos::{
+    authz::v1beta1 as authz,
+    bank::v1beta1 as bank,
+    base::v1beta1::Coin as ProtoCoin,
+    crisis::v1beta1 as crisis,
+    crypto::{ed25519, multisig::LegacyAminoPubKey, secp256k1},
+    distribution::v1beta1 as distribution,
+    evidence::v1beta1 as evidence,
+    feegrant::v1beta1 as feegrant,
+    gov::v1beta1 as gov,
+    slashing::v1beta1 as slashing,
+    staking::v1beta1 as staking,
+    tx::{
+        signing::v1beta1::SignMode as ProtoSignMode,
+        v1beta1::{mode_info, AuthInfo, ModeInfo, SignerInfo, TxBody, TxRaw},
+    },
+    vesting::v1beta1 as vesting,
+};
+#[cfg(feature = "cosmwasm")]
+use cosmos_sdk_proto::cosmwasm::wasm::v1 as wasm;
+use cosmos_sdk_proto::ibc::applications::transfer::v1 as ibc_transfer;
+use prost::Message;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::{error::TxError, utils::hex_bytes};
+
+use super::{amino::rfc3339, Any, Coin};
+
+macro_rules! msgs {
+    ($($(#[$meta:meta])* $variant:ident($ty:ty) = $type_url:literal,)*) => {
+        /// A tx message decoded into its concrete type
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum Msg {
+            $($(#[$meta])* $variant($ty),)*
+            /// A message whose type URL ocular doesn't know. Its value is shown as hex.
+            Unknown(Any),
+        }
+
+        impl Msg {
+            /// Decodes a message into its concrete type. Errors if the type URL is known but the value can't be
+            /// decoded as that type.
+            pub fn decode(any: &Any) -> Result<Msg, TxError> {
+                Ok(match any.type_url.as_str() {
+                    $($(#[$meta])* $type_url => Msg::$variant(decode(any)?),)*
+                    _ => Msg::Unknown(any.clone()),
+                })
+            }
+
+            pub fn type_url(&self) -> &str {
+                match self {
+                    $($(#[$meta])* Msg::$variant(_) => $type_url,)*
+                    Msg::Unknown(any) => &any.type_url,
+                }
+            }
+
+            pub fn to_any(&self) -> Any {
+                match self {
+                    $($(#[$meta])* Msg::$variant(msg) => Any {
+                        type_url: $type_url.to_string(),
+                        value: msg.encode_to_vec(),
+                    },)*
+                    Msg::Unknown(any) => any.clone(),
+                }
+            }
+        }
+    };
+}
+
+msgs! {
+    Send(bank::MsgSend) = "/cosmos.bank.v1beta1.MsgSend",
+    MultiSend(bank::MsgMultiSend) = "/cosmos.bank.v1beta1.MsgMultiSend",
+    Grant(authz::MsgGrant) = "/cosmos.authz.v1beta1.MsgGrant",
+    Exec(authz::MsgExec) = "/cosmos.authz.v1beta1.MsgExec",
+    Revoke(authz::MsgRevoke) = "/cosmos.authz.v1beta1.MsgRevoke",
+    VerifyInvariant(crisis::MsgVerifyInvariant) = "/cosmos.crisis.v1beta1.MsgVerifyInvariant",
+    SetWithdrawAddress(distribution::MsgSetWithdrawAddress) =
+        "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+    WithdrawDelegatorReward(distribution::MsgWithdrawDelegatorReward) =
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+    WithdrawValidatorCommission(distribution::MsgWithdrawValidatorCommission) =
+        "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission",
+    FundCommunityPool(distribution::MsgFundCommunityPool) =
+        "/cosmos.distribution.v1beta1.MsgFundCommunityPool",
+    SubmitEvidence(evidence::MsgSubmitEvidence) = "/cosmos.evidence.v1beta1.MsgSubmitEvidence",
+    GrantAllowance(feegrant::MsgGrantAllowance) = "/cosmos.feegrant.v1beta1.MsgGrantAllowance",
+    RevokeAllowance(feegrant::MsgRevokeAllowance) = "/cosmos.feegrant.v1beta1.MsgRevokeAllowance",
+    SubmitProposal(gov::MsgSubmitProposal) = "/cosmos.gov.v1beta1.MsgSubmitProposal",
+    Vote(gov::MsgVote) = "/cosmos.gov.v1beta1.MsgVote",
+    VoteWeighted(gov::MsgVoteWeighted) = "/cosmos.gov.v1beta1.MsgVoteWeighted",
+    Deposit(gov::MsgDeposit) = "/cosmos.gov.v1beta1.MsgDeposit",
+    Unjail(slashing::MsgUnjail) = "/cosmos.slashing.v1beta1.MsgUnjail",
+    CreateValidator(staking::MsgCreateValidator) = "/cosmos.staking.v1beta1.MsgCreateValidator",
+    EditValidator(staking::MsgEditValidator) = "/cosmos.staking.v1beta1.MsgEditValidator",
+    Delegate(staking::MsgDelegate) = "/cosmos.staking.v1beta1.MsgDelegate",
+    BeginRedelegate(staking::MsgBeginRedelegate) = "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+    Undelegate(staking::MsgUndelegate) = "/cosmos.staking.v1beta1.MsgUndelegate",
+    CreateVestingAccount(vesting::MsgCreateVestingAccount) =
+        "/cosmos.vesting.v1beta1.MsgCreateVestingAccount",
+    Transfer(ibc_transfer::MsgTransfer) = "/ibc.applications.transfer.v1.MsgTransfer",
+    #[cfg(feature = "cosmwasm")]
+    StoreCode(wasm::MsgStoreCode) = "/cosmwasm.wasm.v1.MsgStoreCode",
+    #[cfg(feature = "cosmwasm")]
+    InstantiateContract(wasm::MsgInstantiateContract) =
+        "/cosmwasm.wasm.v1.MsgInstantiateContract",
+    #[cfg(feature = "cosmwasm")]
+    ExecuteContract(wasm::MsgExecuteContract) = "/cosmwasm.wasm.v1.MsgExecuteContract",
+    #[cfg(feature = "cosmwasm")]
+    MigrateContract(wasm::MsgMigrateContract) = "/cosmwasm.wasm.v1.MsgMigrateContract",
+    #[cfg(feature = "cosmwasm")]
+    UpdateAdmin(wasm::MsgUpdateAdmin) = "/cosmwasm.wasm.v1.MsgUpdateAdmin",
+    #[cfg(feature = "cosmwasm")]
+    ClearAdmin(wasm::MsgClearAdmin) = "/cosmwasm.wasm.v1.MsgClearAdmin",
+}
+
+impl Msg {
+    /// Addresses that must sign the message, like the Cosmos SDK's `GetSigners`. Validator operator addresses are
+    /// converted to the operator's account address. Unknown messages have no known signers.
+    pub fn signers(&self) -> Vec<String> {
+        match self {
+            Msg::Send(msg) => vec![msg.from_address.clone()],
+            Msg::MultiSend(msg) => msg.inputs.iter().map(|i| i.address.clone()).collect(),
+            Msg::Grant(msg) => vec![msg.granter.clone()],
+            Msg::Exec(msg) => vec![msg.grantee.clone()],
+            Msg::Revoke(msg) => vec![msg.granter.clone()],
+            Msg::VerifyInvariant(msg) => vec![msg.sender.clone()],
+            Msg::SetWithdrawAddress(msg) => vec![msg.delegator_address.clone()],
+            Msg::WithdrawDelegatorReward(msg) => vec![msg.delegator_address.clone()],
+            Msg::WithdrawValidatorCommission(msg) => {
+                vec![operator_account(&msg.validator_address)]
+            }
+            Msg::FundCommunityPool(msg) => vec![msg.depositor.clone()],
+            Msg::SubmitEvidence(msg) => vec![msg.submitter.clone()],
+            Msg::GrantAllowance(msg) => vec![msg.granter.clone()],
+            Msg::RevokeAllowance(msg) => vec![msg.granter.clone()],
+            Msg::SubmitProposal(msg) => vec![msg.proposer.clone()],
+            Msg::Vote(msg) => vec![msg.voter.clone()],
+            Msg::VoteWeighted(msg) => vec![msg.voter.clone()],
+            Msg::Deposit(msg) => vec![msg.depositor.clone()],
+            Msg::Unjail(msg) => vec![operator_account(&msg.validator_addr)],
+            Msg::CreateValidator(msg) => vec![msg.delegator_address.clone()],
+            Msg::EditValidator(msg) => vec![operator_account(&msg.validator_address)],
+            Msg::Delegate(msg) => vec![msg.delegator_address.clone()],
+            Msg::BeginRedelegate(msg) => vec![msg.delegator_address.clone()],
+            Msg::Undelegate(msg) => vec![msg.delegator_address.clone()],
+            Msg::CreateVestingAccount(msg) => vec![msg.from_address.clone()],
+            Msg::Transfer(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::StoreCode(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::InstantiateContract(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::ExecuteContract(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::MigrateContract(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::UpdateAdmin(msg) => vec![msg.sender.clone()],
+            #[cfg(feature = "cosmwasm")]
+            Msg::ClearAdmin(msg) => vec![msg.sender.clone()],
+            Msg::Unknown(_) => Vec::new(),
+        }
+    }
+
+    /// Converts the message to protobuf JSON with its type URL in the `@type` field
+    pub fn to_json(&self) -> Value {
+        let mut value = match self {
+            Msg::Send(msg) => json!({
+                "from_address": msg.from_address,
+                "to_address": msg.to_address,
+                "amount": coins(&msg.amount),
+            }),
+            Msg::MultiSend(msg) => json!({
+                "inputs": msg.inputs.iter().map(|i| json!({
+                    "address": i.address,
+                    "coins": coins(&i.coins),
+                })).collect::<Vec<Value>>(),
+                "outputs": msg.outputs.iter().map(|o| json!({
+                    "address": o.address,
+                    "coins": coins(&o.coins),
+                })).collect::<Vec<Value>>(),
+            }),
+            Msg::Grant(msg) => json!({
+                "granter": msg.granter,
+                "grantee": msg.grantee,
+                "grant": msg.grant.as_ref().map(|g| json!({
+                    "authorization": g.authorization.as_ref().map(any_to_json),
+                    "expiration": g.expiration.as_ref().map(rfc3339),
+                })),
+            }),
+            Msg::Exec(msg) => json!({
+                "grantee": msg.grantee,
+                "msgs": msg.msgs.iter().map(any_to_json).collect::<Vec<Value>>(),
+            }),
+            Msg::Revoke(msg) => json!({
+                "granter": msg.granter,
+                "grantee": msg.grantee,
+                "msg_type_url": msg.msg_type_url,
+            }),
+            Msg::VerifyInvariant(msg) => json!({
+                "sender": msg.sender,
+                "invariant_module_name": msg.invariant_module_name,
+                "invariant_route": msg.invariant_route,
+            }),
+            Msg::SetWithdrawAddress(msg) => json!({
+                "delegator_address": msg.delegator_address,
+                "withdraw_address": msg.withdraw_address,
+            }),
+            Msg::WithdrawDelegatorReward(msg) => json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+            }),
+            Msg::WithdrawValidatorCommission(msg) => json!({
+                "validator_address": msg.validator_address,
+            }),
+            Msg::FundCommunityPool(msg) => json!({
+                "amount": coins(&msg.amount),
+                "depositor": msg.depositor,
+            }),
+            Msg::SubmitEvidence(msg) => json!({
+                "submitter": msg.submitter,
+                "evidence": msg.evidence.as_ref().map(any_to_json),
+            }),
+            Msg::GrantAllowance(msg) => json!({
+                "granter": msg.granter,
+                "grantee": msg.grantee,
+                "allowance": msg.allowance.as_ref().map(any_to_json),
+            }),
+            Msg::RevokeAllowance(msg) => json!({
+                "granter": msg.granter,
+                "grantee": msg.grantee,
+            }),
+            Msg::SubmitProposal(msg) => json!({
+                "content": msg.content.as_ref().map(any_to_json),
+                "initial_deposit": coins(&msg.initial_deposit),
+                "proposer": msg.proposer,
+            }),
+            Msg::Vote(msg) => json!({
+                "proposal_id": msg.proposal_id.to_string(),
+                "voter": msg.voter,
+                "option": vote_option(msg.option),
+            }),
+            Msg::VoteWeighted(msg) => json!({
+                "proposal_id": msg.proposal_id.to_string(),
+                "voter": msg.voter,
+                "options": msg.options.iter().map(|o| json!({
+                    "option": vote_option(o.option),
+                    "weight": o.weight,
+                })).collect::<Vec<Value>>(),
+            }),
+            Msg::Deposit(msg) => json!({
+                "proposal_id": msg.proposal_id.to_string(),
+                "depositor": msg.depositor,
+                "amount": coins(&msg.amount),
+            }),
+            Msg::Unjail(msg) => json!({ "validator_addr": msg.validator_addr }),
+            Msg::CreateValidator(msg) => json!({
+                "description": msg.description.as_ref().map(description),
+                "commission": msg.commission.as_ref().map(|c| json!({
+                    "rate": c.rate,
+                    "max_rate": c.max_rate,
+                    "max_change_rate": c.max_change_rate,
+                })),
+                "min_self_delegation": msg.min_self_delegation,
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "pubkey": msg.pubkey.as_ref().map(any_to_json),
+                "value": msg.value.as_ref().map(coin),
+            }),
+            Msg::EditValidator(msg) => json!({
+                "description": msg.description.as_ref().map(description),
+                "validator_address": msg.validator_address,
+                "commission_rate": msg.commission_rate,
+                "min_self_delegation": msg.min_self_delegation,
+            }),
+            Msg::Delegate(msg) => json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+            Msg::BeginRedelegate(msg) => json!({
+                "delegator_address": msg.delegator_address,
+                "validator_src_address": msg.validator_src_address,
+                "validator_dst_address": msg.validator_dst_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+            Msg::Undelegate(msg) => json!({
+                "delegator_address": msg.delegator_address,
+                "validator_address": msg.validator_address,
+                "amount": msg.amount.as_ref().map(coin),
+            }),
+            Msg::CreateVestingAccount(msg) => json!({
+                "from_address": msg.from_address,
+                "to_address": msg.to_address,
+                "amount": coins(&msg.amount),
+                "end_time": msg.end_time.to_string(),
+                "delayed": msg.delayed,
+            }),
+            Msg::Transfer(msg) => json!({
+                "source_port": msg.source_port,
+                "source_channel": msg.source_channel,
+                "token": msg.token.as_ref().map(coin),
+                "sender": msg.sender,
+                "receiver": msg.receiver,
+                "timeout_height": msg.timeout_height.as_ref().map(|h| json!({
+                    "revision_number": h.revision_number.to_string(),
+                    "revision_height": h.revision_height.to_string(),
+                })),
+                "timeout_timestamp": msg.timeout_timestamp.to_string(),
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::StoreCode(msg) => json!({
+                "sender": msg.sender,
+                "wasm_byte_code": base64::encode(&msg.wasm_byte_code),
+                "instantiate_permission": msg.instantiate_permission.as_ref().map(|p| json!({
+                    "permission": access_type(p.permission),
+                    "address": p.address,
+                })),
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::InstantiateContract(msg) => json!({
+                "sender": msg.sender,
+                "admin": msg.admin,
+                "code_id": msg.code_id.to_string(),
+                "label": msg.label,
+                "msg": contract_msg(&msg.msg),
+                "funds": coins(&msg.funds),
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::ExecuteContract(msg) => json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "msg": contract_msg(&msg.msg),
+                "funds": coins(&msg.funds),
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::MigrateContract(msg) => json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "code_id": msg.code_id.to_string(),
+                "msg": contract_msg(&msg.msg),
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::UpdateAdmin(msg) => json!({
+                "sender": msg.sender,
+                "new_admin": msg.new_admin,
+                "contract": msg.contract,
+            }),
+            #[cfg(feature = "cosmwasm")]
+            Msg::ClearAdmin(msg) => json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+            }),
+            Msg::Unknown(any) => json!({ "value": hex::encode(&any.value) }),
+        };
+
+        value["@type"] = self.type_url().into();
+
+        value
+    }
+}
+
+impl Serialize for Msg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+/// A decoded tx. Serializes to JSON with `serde_json` or [`DecodedTx::to_json`].
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedTx {
+    pub body: DecodedTxBody,
+    pub auth_info: DecodedAuthInfo,
+    /// Addresses required to sign the tx's messages, in the order they first appear
+    pub signers: Vec<String>,
+    #[serde(with = "hex_bytes::vec")]
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl DecodedTx {
+    /// Decodes protobuf encoded `TxRaw` bytes, the format txs are broadcast and stored in blocks in
+    pub fn from_bytes(tx_bytes: &[u8]) -> Result<DecodedTx, TxError> {
+        let tx_raw = TxRaw::decode(tx_bytes).map_err(|e| TxError::Serialization(e.to_string()))?;
+        let body = TxBody::decode(&tx_raw.body_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let auth_info = AuthInfo::decode(&tx_raw.auth_info_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let messages = body
+            .messages
+            .iter()
+            .map(Msg::decode)
+            .collect::<Result<Vec<Msg>, TxError>>()?;
+        let mut signers = Vec::<String>::new();
+
+        for signer in messages.iter().flat_map(Msg::signers) {
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+
+        let fee = auth_info.fee.unwrap_or_default();
+
+        Ok(DecodedTx {
+            body: DecodedTxBody {
+                messages,
+                memo: body.memo,
+                timeout_height: body.timeout_height,
+            },
+            auth_info: DecodedAuthInfo {
+                signer_infos: auth_info
+                    .signer_infos
+                    .into_iter()
+                    .map(DecodedSignerInfo::from)
+                    .collect(),
+                fee: DecodedFee {
+                    amount: fee
+                        .amount
+                        .into_iter()
+                        .map(Coin::try_from)
+                        .collect::<Result<Vec<Coin>, _>>()?,
+                    gas_limit: fee.gas_limit,
+                    payer: fee.payer,
+                    granter: fee.granter,
+                },
+            },
+            signers,
+            signatures: tx_raw.signatures,
+        })
+    }
+
+    /// Decodes base64 encoded tx bytes, as returned by the RPC and REST endpoints
+    pub fn from_base64(tx: &str) -> Result<DecodedTx, TxError> {
+        let tx_bytes =
+            base64::decode(tx.trim()).map_err(|e| TxError::Serialization(e.to_string()))?;
+
+        DecodedTx::from_bytes(&tx_bytes)
+    }
+
+    pub fn to_json(&self) -> Result<String, TxError> {
+        serde_json::to_string(self).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedTxBody {
+    pub messages: Vec<Msg>,
+    pub memo: String,
+    #[serde(serialize_with = "serialize_u64")]
+    pub timeout_height: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedAuthInfo {
+    pub signer_infos: Vec<DecodedSignerInfo>,
+    pub fee: DecodedFee,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedSignerInfo {
+    #[serde(serialize_with = "serialize_public_key")]
+    pub public_key: Option<Any>,
+    #[serde(serialize_with = "serialize_mode_info")]
+    pub mode_info: Option<ModeInfo>,
+    #[serde(serialize_with = "serialize_u64")]
+    pub sequence: u64,
+}
+
+impl From<SignerInfo> for DecodedSignerInfo {
+    fn from(signer_info: SignerInfo) -> DecodedSignerInfo {
+        DecodedSignerInfo {
+            public_key: signer_info.public_key,
+            mode_info: signer_info.mode_info,
+            sequence: signer_info.sequence,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DecodedFee {
+    pub amount: Vec<Coin>,
+    #[serde(serialize_with = "serialize_u64")]
+    pub gas_limit: u64,
+    pub payer: String,
+    pub granter: String,
+}
+
+/// Protobuf JSON encodes 64 bit integers as strings
+fn serialize_u64<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn serialize_public_key<S: Serializer>(
+    public_key: &Option<Any>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    public_key.as_ref().map(any_to_json).serialize(serializer)
+}
+
+fn serialize_mode_info<S: Serializer>(
+    mode_info: &Option<ModeInfo>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    mode_info
+        .as_ref()
+        .map(mode_info_to_json)
+        .serialize(serializer)
+}
+
+fn mode_info_to_json(mode_info: &ModeInfo) -> Value {
+    match &mode_info.sum {
+        Some(mode_info::Sum::Single(single)) => json!({
+            "single": { "mode": sign_mode(single.mode) },
+        }),
+        Some(mode_info::Sum::Multi(multi)) => json!({
+            "multi": {
+                "bitarray": multi.bitarray.as_ref().map(|b| json!({
+                    "extra_bits_stored": b.extra_bits_stored,
+                    "elems": hex::encode(&b.elems),
+                })),
+                "mode_infos": multi.mode_infos.iter().map(mode_info_to_json).collect::<Vec<Value>>(),
+            },
+        }),
+        None => Value::Null,
+    }
+}
+
+/// Decodes a nested `Any`: messages, authorizations, allowances, proposal content and public keys. Anything else,
+/// or anything that fails to decode, is shown as hex.
+fn any_to_json(any: &Any) -> Value {
+    match nested_to_json(any) {
+        Ok(Some(mut value)) => {
+            value["@type"] = any.type_url.clone().into();
+            value
+        }
+        Ok(None) | Err(_) => match Msg::decode(any) {
+            Ok(msg) => msg.to_json(),
+            Err(_) => json!({ "@type": any.type_url, "value": hex::encode(&any.value) }),
+        },
+    }
+}
+
+fn nested_to_json(any: &Any) -> Result<Option<Value>, TxError> {
+    Ok(Some(match any.type_url.as_str() {
+        "/cosmos.authz.v1beta1.GenericAuthorization" => {
+            let authorization: authz::GenericAuthorization = decode(any)?;
+
+            json!({ "msg": authorization.msg })
+        }
+        "/cosmos.bank.v1beta1.SendAuthorization" => {
+            let authorization: bank::SendAuthorization = decode(any)?;
+
+            json!({ "spend_limit": coins(&authorization.spend_limit) })
+        }
+        "/cosmos.staking.v1beta1.StakeAuthorization" => {
+            let authorization: staking::StakeAuthorization = decode(any)?;
+            let (allow_list, deny_list) = match authorization.validators {
+                Some(staking::stake_authorization::Policy::AllowList(v)) => (Some(v.address), None),
+                Some(staking::stake_authorization::Policy::DenyList(v)) => (None, Some(v.address)),
+                None => (None, None),
+            };
+
+            json!({
+                "max_tokens": authorization.max_tokens.as_ref().map(coin),
+                "allow_list": allow_list.map(|a| json!({ "address": a })),
+                "deny_list": deny_list.map(|a| json!({ "address": a })),
+                "authorization_type": authorization_type(authorization.authorization_type),
+            })
+        }
+        "/cosmos.feegrant.v1beta1.BasicAllowance" => {
+            let allowance: feegrant::BasicAllowance = decode(any)?;
+
+            basic_allowance(&allowance)
+        }
+        "/cosmos.feegrant.v1beta1.PeriodicAllowance" => {
+            let allowance: feegrant::PeriodicAllowance = decode(any)?;
+
+            json!({
+                "basic": allowance.basic.as_ref().map(basic_allowance),
+                "period": allowance.period.as_ref().map(|d| duration(d.seconds, d.nanos)),
+                "period_spend_limit": coins(&allowance.period_spend_limit),
+                "period_can_spend": coins(&allowance.period_can_spend),
+                "period_reset": allowance.period_reset.as_ref().map(rfc3339),
+            })
+        }
+        "/cosmos.feegrant.v1beta1.AllowedMsgAllowance" => {
+            let allowance: feegrant::AllowedMsgAllowance = decode(any)?;
+
+            json!({
+                "allowance": allowance.allowance.as_ref().map(any_to_json),
+                "allowed_messages": allowance.allowed_messages,
+            })
+        }
+        "/cosmos.gov.v1beta1.TextProposal" => {
+            let proposal: gov::TextProposal = decode(any)?;
+
+            json!({ "title": proposal.title, "description": proposal.description })
+        }
+        "/cosmos.crypto.secp256k1.PubKey" => {
+            let key: secp256k1::PubKey = decode(any)?;
+
+            json!({ "key": hex::encode(&key.key) })
+        }
+        "/cosmos.crypto.ed25519.PubKey" => {
+            let key: ed25519::PubKey = decode(any)?;
+
+            json!({ "key": hex::encode(&key.key) })
+        }
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            let key: LegacyAminoPubKey = decode(any)?;
+
+            json!({
+                "threshold": key.threshold,
+                "public_keys": key.public_keys.iter().map(any_to_json).collect::<Vec<Value>>(),
+            })
+        }
+        _ => return Ok(None),
+    }))
+}
+
+fn decode<M: Message + Default>(any: &Any) -> Result<M, TxError> {
+    M::decode(&any.value[..]).map_err(|e| TxError::Serialization(e.to_string()))
+}
+
+fn coin(coin: &ProtoCoin) -> Value {
+    json!({ "denom": coin.denom, "amount": coin.amount })
+}
+
+fn coins(coins: &[ProtoCoin]) -> Value {
+    coins.iter().map(coin).collect()
+}
+
+fn basic_allowance(allowance: &feegrant::BasicAllowance) -> Value {
+    json!({
+        "spend_limit": coins(&allowance.spend_limit),
+        "expiration": allowance.expiration.as_ref().map(rfc3339),
+    })
+}
+
+fn description(description: &staking::Description) -> Value {
+    json!({
+        "moniker": description.moniker,
+        "identity": description.identity,
+        "website": description.website,
+        "security_contact": description.security_contact,
+        "details": description.details,
+    })
+}
+
+/// Formats a duration like protobuf JSON, e.g. `"3600s"` or `"1.5s"`
+fn duration(seconds: i64, nanos: i32) -> String {
+    if nanos == 0 {
+        return format!("{}s", seconds);
+    }
+
+    let nanos = format!("{:09}", nanos.unsigned_abs());
+
+    format!("{}.{}s", seconds, nanos.trim_end_matches('0'))
+}
+
+fn vote_option(option: i32) -> Value {
+    match gov::VoteOption::from_i32(option) {
+        Some(gov::VoteOption::Unspecified) => "VOTE_OPTION_UNSPECIFIED".into(),
+        Some(gov::VoteOption::Yes) => "VOTE_OPTION_YES".into(),
+        Some(gov::VoteOption::Abstain) => "VOTE_OPTION_ABSTAIN".into(),
+        Some(gov::VoteOption::No) => "VOTE_OPTION_NO".into(),
+        Some(gov::VoteOption::NoWithVeto) => "VOTE_OPTION_NO_WITH_VETO".into(),
+        None => option.into(),
+    }
+}
+
+fn authorization_type(authorization_type: i32) -> Value {
+    match staking::AuthorizationType::from_i32(authorization_type) {
+        Some(staking::AuthorizationType::Unspecified) => "AUTHORIZATION_TYPE_UNSPECIFIED".into(),
+        Some(staking::AuthorizationType::Delegate) => "AUTHORIZATION_TYPE_DELEGATE".into(),
+        Some(staking::AuthorizationType::Undelegate) => "AUTHORIZATION_TYPE_UNDELEGATE".into(),
+        Some(staking::AuthorizationType::Redelegate) => "AUTHORIZATION_TYPE_REDELEGATE".into(),
+        None => authorization_type.into(),
+    }
+}
+
+/// Contract messages are shown as the JSON they hold, like wasmd does, or as base64 if they aren't valid JSON
+#[cfg(feature = "cosmwasm")]
+fn contract_msg(msg: &[u8]) -> Value {
+    serde_json::from_slice(msg).unwrap_or_else(|_| base64::encode(msg).into())
+}
+
+#[cfg(feature = "cosmwasm")]
+fn access_type(access_type: i32) -> Value {
+    match wasm::AccessType::from_i32(access_type) {
+        Some(wasm::AccessType::Unspecified) => "ACCESS_TYPE_UNSPECIFIED".into(),
+        Some(wasm::AccessType::Nobody) => "ACCESS_TYPE_NOBODY".into(),
+        Some(wasm::AccessType::OnlyAddress) => "ACCESS_TYPE_ONLY_ADDRESS".into(),
+        Some(wasm::AccessType::Everybody) => "ACCESS_TYPE_EVERYBODY".into(),
+        None => access_type.into(),
+    }
+}
+
+fn sign_mode(mode: i32) -> Value {
+    match ProtoSignMode::from_i32(mode) {
+        Some(ProtoSignMode::Unspecified) => "SIGN_MODE_UNSPECIFIED".into(),
+        Some(ProtoSignMode::Direct) => "SIGN_MODE_DIRECT".into(),
+        Some(ProtoSignMode::Textual) => "SIGN_MODE_TEXTUAL".into(),
+        Some(ProtoSignMode::LegacyAminoJson) => "SIGN_MODE_LEGACY_AMINO_JSON".into(),
+        _ => mode.into(),
+    }
+}
+
+/// Converts a validator operator address (e.g. `cosmosvaloper1...`) to the operator's account address. Returns the
+/// address unchanged if it isn't a valid operator address.
+fn operator_account(operator_address: &str) -> String {
+    match bech32::decode(operator_address) {
+        Ok((hrp, data, variant)) if hrp.ends_with("valoper") => {
+            bech32::encode(hrp.trim_end_matches("valoper"), data, variant)
+                .unwrap_or_else(|_| operator_address.to_string())
+        }
+        _ => operator_address.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee;
+    use cosmrs::AccountId;
+
+    fn any<M: Message>(type_url: &str, msg: &M) -> Any {
+        Any {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn tx_bytes(messages: Vec<Any>) -> Vec<u8> {
+        let body = TxBody {
+            messages,
+            memo: "decode me".to_string(),
+            timeout_height: 100,
+            extension_options: Vec::new(),
+            non_critical_extension_options: Vec::new(),
+        };
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(any(
+                    "/cosmos.crypto.secp256k1.PubKey",
+                    &secp256k1::PubKey { key: vec![2; 33] },
+                )),
+                mode_info: Some(ModeInfo {
+                    sum: Some(mode_info::Sum::Single(mode_info::Single {
+                        mode: ProtoSignMode::Direct as i32,
+                    })),
+                }),
+                sequence: 7,
+            }],
+            fee: Some(Fee {
+                amount: vec![ProtoCoin {
+                    denom: "uatom".to_string(),
+                    amount: "5000".to_string(),
+                }],
+                gas_limit: 200000,
+                payer: String::new(),
+                granter: String::new(),
+            }),
+        };
+
+        TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![vec![1, 2, 3]],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn decodes_tx() {
+        let send = bank::MsgSend {
+            from_address: "cosmos1from".to_string(),
+            to_address: "cosmos1to".to_string(),
+            amount: vec![ProtoCoin {
+                denom: "uatom".to_string(),
+                amount: "10".to_string(),
+            }],
+        };
+        let exec = authz::MsgExec {
+            grantee: "cosmos1grantee".to_string(),
+            msgs: vec![any("/cosmos.bank.v1beta1.MsgSend", &send)],
+        };
+        let unknown = Any {
+            type_url: "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn".to_string(),
+            value: vec![0xab, 0xcd],
+        };
+        let tx_bytes = tx_bytes(vec![
+            any("/cosmos.bank.v1beta1.MsgSend", &send),
+            any("/cosmos.authz.v1beta1.MsgExec", &exec),
+            unknown,
+        ]);
+        let tx = DecodedTx::from_bytes(&tx_bytes).unwrap();
+
+        assert_eq!(tx.body.messages[0], Msg::Send(send));
+        assert!(matches!(tx.body.messages[1], Msg::Exec(_)));
+        assert!(matches!(tx.body.messages[2], Msg::Unknown(_)));
+        assert_eq!(tx.signers, vec!["cosmos1from", "cosmos1grantee"]);
+        assert_eq!(tx.auth_info.fee.amount, vec![Coin::new(5000, "uatom")]);
+        assert_eq!(tx.auth_info.signer_infos[0].sequence, 7);
+
+        let json: Value = serde_json::from_str(&tx.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["body"]["memo"], "decode me");
+        assert_eq!(json["body"]["timeout_height"], "100");
+        assert_eq!(json["auth_info"]["signer_infos"][0]["sequence"], "7");
+        assert_eq!(json["auth_info"]["fee"]["gas_limit"], "200000");
+        assert_eq!(
+            json["body"]["messages"][0]["@type"],
+            "/cosmos.bank.v1beta1.MsgSend"
+        );
+        assert_eq!(json["body"]["messages"][0]["amount"][0]["amount"], "10");
+        assert_eq!(
+            json["body"]["messages"][1]["msgs"][0]["to_address"],
+            "cosmos1to"
+        );
+        assert_eq!(json["body"]["messages"][2]["value"], "abcd");
+        assert_eq!(
+            json["auth_info"]["signer_infos"][0]["mode_info"]["single"]["mode"],
+            "SIGN_MODE_DIRECT"
+        );
+        assert_eq!(
+            json["auth_info"]["signer_infos"][0]["public_key"]["@type"],
+            "/cosmos.crypto.secp256k1.PubKey"
+        );
+        assert_eq!(json["signatures"][0], "010203");
+
+        let encoded = base64::encode(&tx_bytes);
+
+        assert_eq!(
+            DecodedTx::from_base64(&encoded).unwrap().signers,
+            tx.signers
+        );
+    }
+
+    #[test]
+    fn decodes_ibc_transfers() {
+        let transfer = ibc_transfer::MsgTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-141".to_string(),
+            token: Some(ProtoCoin {
+                denom: "uatom".to_string(),
+                amount: "10".to_string(),
+            }),
+            sender: "cosmos1sender".to_string(),
+            receiver: "osmo1receiver".to_string(),
+            timeout_height: None,
+            timeout_timestamp: 1660000000000000000,
+        };
+        let msg =
+            Msg::decode(&any("/ibc.applications.transfer.v1.MsgTransfer", &transfer)).unwrap();
+
+        assert_eq!(msg, Msg::Transfer(transfer));
+        assert_eq!(msg.signers(), vec!["cosmos1sender"]);
+
+        let json = msg.to_json();
+
+        assert_eq!(json["source_channel"], "channel-141");
+        assert_eq!(json["token"]["amount"], "10");
+        assert_eq!(json["timeout_timestamp"], "1660000000000000000");
+        assert_eq!(json["timeout_height"], Value::Null);
+    }
+
+    #[cfg(feature = "cosmwasm")]
+    #[test]
+    fn decodes_contract_msgs() {
+        let execute = wasm::MsgExecuteContract {
+            sender: "juno1sender".to_string(),
+            contract: "juno1contract".to_string(),
+            msg: br#"{"transfer":{"amount":"5"}}"#.to_vec(),
+            funds: vec![],
+        };
+        let msg = Msg::decode(&any("/cosmwasm.wasm.v1.MsgExecuteContract", &execute)).unwrap();
+
+        assert_eq!(msg, Msg::ExecuteContract(execute));
+        assert_eq!(msg.signers(), vec!["juno1sender"]);
+        assert_eq!(msg.to_json()["msg"]["transfer"]["amount"], "5");
+    }
+
+    #[test]
+    fn converts_operator_addresses() {
+        let account = AccountId::new("cosmos", &[1; 20]).unwrap();
+        let operator = AccountId::new("cosmosvaloper", &[1; 20]).unwrap();
+        let unjail = Msg::Unjail(slashing::MsgUnjail {
+            validator_addr: operator.to_string(),
+        });
+
+        assert_eq!(unjail.signers(), vec![account.to_string()]);
+        assert_eq!(duration(3600, 0), "3600s");
+        assert_eq!(duration(1, 500000000), "1.5s");
+    }
+}
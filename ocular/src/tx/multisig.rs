@@ -0,0 +1,441 @@
+//! Legacy amino multisig accounts, compatible with keys created by `<daemon> keys add --multisig`.
+//!
+//! A multisig tx is built once with [`crate::tx::TxBuilder::build_multisig`], signed by each member with
+//! [`UnsignedTx::sign_partial`], and the partial signatures are combined with [`MultisigAccount::combine`] into a
+//! [`SignedTx`] that can be broadcast with [`crate::chain::client::ChainClient::broadcast_signed_tx`].
+use cosmos_sdk_proto::cosmos::{
+    crypto::multisig::{
+        v1beta1::{CompactBitArray, MultiSignature},
+        LegacyAminoPubKey,
+    },
+    tx::v1beta1::{mode_info, mode_info::Sum, AuthInfo, ModeInfo, SignerInfo},
+};
+use cosmrs::{tx, AccountId};
+use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    account::PublicKey,
+    error::{AccountError, TxError},
+    utils::hex_bytes,
+};
+
+use super::{
+    builder::{fee, single_mode_info},
+    Any, SignedTx, SignerData, TxMetadata, UnsignedTx,
+};
+
+pub const LEGACY_AMINO_MULTISIG_TYPE_URL: &str = "/cosmos.crypto.multisig.LegacyAminoPubKey";
+const SECP256K1_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+// Amino prefix bytes of tendermint/PubKeyMultisigThreshold and tendermint/PubKeySecp256k1
+const AMINO_MULTISIG_PREFIX: [u8; 4] = [0x22, 0xc1, 0xf7, 0xe2];
+const AMINO_SECP256K1_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// A threshold multisig public key made up of secp256k1 member keys. The order of the keys is part of the key
+/// itself, so it must match the order used when the account was created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigAccount {
+    threshold: u32,
+    public_keys: Vec<PublicKey>,
+}
+
+impl MultisigAccount {
+    /// Creates a multisig with the keys in the given order. Use [`MultisigAccount::new_sorted`] for multisigs
+    /// created with the Go CLI, which sorts keys by address unless `--nosort` is passed.
+    pub fn new(
+        threshold: u32,
+        public_keys: Vec<PublicKey>,
+    ) -> Result<MultisigAccount, AccountError> {
+        if threshold == 0 {
+            return Err(AccountError::InvalidMultisig(
+                "threshold must be greater than 0".to_string(),
+            ));
+        }
+        if public_keys.len() < threshold as usize {
+            return Err(AccountError::InvalidMultisig(format!(
+                "threshold {} is greater than the number of keys {}",
+                threshold,
+                public_keys.len()
+            )));
+        }
+        if let Some(key) = public_keys
+            .iter()
+            .find(|k| k.type_url() != SECP256K1_TYPE_URL)
+        {
+            return Err(AccountError::InvalidPublicKey(key.type_url().to_string()));
+        }
+
+        Ok(MultisigAccount {
+            threshold,
+            public_keys,
+        })
+    }
+
+    /// Creates a multisig with the keys sorted by address
+    pub fn new_sorted(
+        threshold: u32,
+        mut public_keys: Vec<PublicKey>,
+    ) -> Result<MultisigAccount, AccountError> {
+        // the prefix doesn't affect the address bytes
+        public_keys.sort_by_cached_key(|k| {
+            k.account_id("cosmos")
+                .map(|id| id.to_bytes())
+                .unwrap_or_default()
+        });
+
+        MultisigAccount::new(threshold, public_keys)
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    pub fn address(&self, prefix: &str) -> Result<String, AccountError> {
+        Ok(self.id(prefix)?.as_ref().to_string())
+    }
+
+    /// The account ID is the first 20 bytes of the SHA256 hash of the amino encoded public key
+    pub fn id(&self, prefix: &str) -> Result<AccountId, AccountError> {
+        let hash = Sha256::digest(self.amino_bytes());
+
+        Ok(AccountId::new(prefix, &hash[..20])?)
+    }
+
+    /// The legacy amino binary encoding of the public key
+    pub fn amino_bytes(&self) -> Vec<u8> {
+        let mut bytes = AMINO_MULTISIG_PREFIX.to_vec();
+
+        bytes.push(0x08);
+        prost::encoding::encode_varint(self.threshold.into(), &mut bytes);
+
+        for key in &self.public_keys {
+            let key_bytes = key.to_bytes();
+            let mut encoded_key = AMINO_SECP256K1_PREFIX.to_vec();
+
+            prost::encoding::encode_varint(key_bytes.len() as u64, &mut encoded_key);
+            encoded_key.extend(key_bytes);
+
+            bytes.push(0x12);
+            prost::encoding::encode_varint(encoded_key.len() as u64, &mut bytes);
+            bytes.extend(encoded_key);
+        }
+
+        bytes
+    }
+
+    pub fn to_any(&self) -> Any {
+        Any {
+            type_url: LEGACY_AMINO_MULTISIG_TYPE_URL.to_string(),
+            value: LegacyAminoPubKey {
+                threshold: self.threshold,
+                public_keys: self.public_keys.iter().map(|k| Any::from(*k)).collect(),
+            }
+            .encode_to_vec(),
+        }
+    }
+
+    /// Builds the sign doc. Each of `signers` must be a member of the multisig and there must be
+    /// at least `threshold` of them.
+    pub fn unsigned_tx(
+        &self,
+        tx_body: &tx::Body,
+        signers: &[PublicKey],
+        signer_data: &SignerData,
+        tx_metadata: &TxMetadata,
+    ) -> Result<UnsignedTx, TxError> {
+        let indices = self.signer_indices(signers.iter().map(|k| k.to_bytes()))?;
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(self.to_any()),
+                mode_info: Some(ModeInfo {
+                    sum: Some(Sum::Multi(mode_info::Multi {
+                        bitarray: Some(self.bitarray(&indices)),
+                        mode_infos: vec![single_mode_info(tx_metadata.sign_mode); indices.len()],
+                    })),
+                }),
+                sequence: signer_data.sequence,
+            }],
+            fee: Some(fee(tx_metadata)),
+        };
+
+        UnsignedTx::from_auth_info_bytes(tx_body, auth_info.encode_to_vec(), signer_data)
+    }
+
+    /// Combines partial signatures into a tx ready to be broadcast. There must be exactly one valid signature from
+    /// each of the signers the tx was built with.
+    pub fn combine(
+        &self,
+        unsigned_tx: &UnsignedTx,
+        signatures: &[PartialSignature],
+    ) -> Result<SignedTx, TxError> {
+        let auth_info = AuthInfo::decode(&unsigned_tx.auth_info_bytes[..])
+            .map_err(|e| TxError::Serialization(e.to_string()))?;
+        let bitarray = auth_info
+            .signer_infos
+            .first()
+            .and_then(|s| s.mode_info.as_ref())
+            .and_then(|m| match &m.sum {
+                Some(Sum::Multi(multi)) => multi.bitarray.clone(),
+                _ => None,
+            })
+            .ok_or_else(|| TxError::Signing("tx was not built for a multisig".to_string()))?;
+
+        let indices = self.signer_indices(signatures.iter().map(|s| s.public_key.clone()))?;
+        // signatures are ordered by the signer's position in the multisig
+        let mut signatures: Vec<&PartialSignature> = signatures.iter().collect();
+        signatures.sort_by_key(|s| {
+            self.public_keys
+                .iter()
+                .position(|k| k.to_bytes() == s.public_key)
+        });
+
+        if self.bitarray(&indices) != bitarray {
+            return Err(TxError::Signing(
+                "signers do not match the signers the tx was built for".to_string(),
+            ));
+        }
+
+        let sign_bytes = unsigned_tx.sign_bytes()?;
+        for partial in &signatures {
+            partial.verify(&sign_bytes)?;
+        }
+
+        let multi_signature = MultiSignature {
+            signatures: signatures.iter().map(|s| s.signature.clone()).collect(),
+        };
+
+        Ok(SignedTx {
+            body_bytes: unsigned_tx.body_bytes.clone(),
+            auth_info_bytes: unsigned_tx.auth_info_bytes.clone(),
+            signatures: vec![multi_signature.encode_to_vec()],
+        })
+    }
+
+    /// Returns the indices of `signers` in the multisig's key list, sorted ascending
+    fn signer_indices(
+        &self,
+        signers: impl Iterator<Item = Vec<u8>>,
+    ) -> Result<Vec<usize>, TxError> {
+        let mut indices = Vec::new();
+
+        for signer in signers {
+            let index = self
+                .public_keys
+                .iter()
+                .position(|k| k.to_bytes() == signer)
+                .ok_or_else(|| {
+                    TxError::Signing(format!(
+                        "{} is not a member of the multisig",
+                        hex::encode(&signer)
+                    ))
+                })?;
+
+            if indices.contains(&index) {
+                return Err(TxError::Signing(format!(
+                    "duplicate signer {}",
+                    hex::encode(&signer)
+                )));
+            }
+
+            indices.push(index);
+        }
+
+        if indices.len() < self.threshold as usize {
+            return Err(TxError::Signing(format!(
+                "{} signers do not meet the threshold of {}",
+                indices.len(),
+                self.threshold
+            )));
+        }
+
+        indices.sort_unstable();
+
+        Ok(indices)
+    }
+
+    fn bitarray(&self, indices: &[usize]) -> CompactBitArray {
+        let size = self.public_keys.len();
+        let mut elems = vec![0u8; (size + 7) / 8];
+
+        for i in indices {
+            elems[i / 8] |= 1 << (7 - i % 8);
+        }
+
+        CompactBitArray {
+            extra_bits_stored: (size % 8) as u32,
+            elems,
+        }
+    }
+}
+
+impl TryFrom<Any> for MultisigAccount {
+    type Error = AccountError;
+
+    fn try_from(any: Any) -> Result<MultisigAccount, Self::Error> {
+        if any.type_url != LEGACY_AMINO_MULTISIG_TYPE_URL {
+            return Err(AccountError::InvalidPublicKey(any.type_url));
+        }
+
+        let key = LegacyAminoPubKey::decode(&any.value[..])
+            .map_err(|e| AccountError::InvalidMultisig(e.to_string()))?;
+        let public_keys = key
+            .public_keys
+            .into_iter()
+            .map(PublicKey::try_from)
+            .collect::<Result<Vec<PublicKey>, _>>()?;
+
+        MultisigAccount::new(key.threshold, public_keys)
+    }
+}
+
+/// A single member's signature over a multisig tx
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct PartialSignature {
+    /// Compressed public key of the signer
+    #[serde(with = "hex_bytes")]
+    pub public_key: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl PartialSignature {
+    pub fn from_json(json: &str) -> Result<PartialSignature, TxError> {
+        serde_json::from_str(json).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, TxError> {
+        serde_json::to_string(self).map_err(|e| TxError::Serialization(e.to_string()))
+    }
+
+    fn verify(&self, sign_bytes: &[u8]) -> Result<(), TxError> {
+        let key = VerifyingKey::from_sec1_bytes(&self.public_key)
+            .map_err(|e| TxError::Signing(e.to_string()))?;
+        let signature = Signature::try_from(&self.signature[..])
+            .map_err(|e| TxError::Signing(e.to_string()))?;
+
+        key.verify(sign_bytes, &signature).map_err(|_| {
+            TxError::Signing(format!(
+                "invalid signature from {}",
+                hex::encode(&self.public_key)
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account::AccountInfo,
+        tx::{Coin, TxBuilder},
+    };
+    use cosmrs::{bank::MsgSend, tx::Msg};
+
+    fn members(n: usize) -> Vec<AccountInfo> {
+        (0..n).map(|_| AccountInfo::new("")).collect()
+    }
+
+    fn unsigned_tx(multisig: &MultisigAccount, signers: &[&AccountInfo]) -> UnsignedTx {
+        let msg = MsgSend {
+            from_address: multisig.id("cosmos").unwrap(),
+            to_address: AccountInfo::new("").id("cosmos").unwrap(),
+            amount: vec![cosmrs::Coin::try_from(Coin {
+                amount: 1,
+                denom: "uatom".to_string(),
+            })
+            .unwrap()],
+        }
+        .to_any()
+        .unwrap();
+        let signer_keys: Vec<PublicKey> = signers.iter().map(|s| s.public_key()).collect();
+
+        TxBuilder::new(SignerData {
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 1,
+            sequence: 0,
+        })
+        .with_msg(msg)
+        .with_tx_metadata(TxMetadata {
+            fee: vec![Coin {
+                amount: 0,
+                denom: "uatom".to_string(),
+            }],
+            gas_limit: 200000,
+            ..Default::default()
+        })
+        .build_multisig(multisig, &signer_keys)
+        .unwrap()
+    }
+
+    #[test]
+    fn derives_multisig_address() {
+        let keys: Vec<PublicKey> = members(3).iter().map(|m| m.public_key()).collect();
+        let multisig = MultisigAccount::new(2, keys.clone()).unwrap();
+        let amino = multisig.amino_bytes();
+
+        assert_eq!(&amino[..6], &[0x22, 0xc1, 0xf7, 0xe2, 0x08, 0x02]);
+        assert_eq!(&amino[6..13], &[0x12, 0x26, 0xeb, 0x5a, 0xe9, 0x87, 0x21]);
+        assert_eq!(&amino[13..46], &keys[0].to_bytes()[..]);
+        assert_eq!(amino.len(), 6 + 3 * 40);
+        assert_eq!(
+            MultisigAccount::try_from(multisig.to_any()).unwrap(),
+            multisig
+        );
+
+        let hash = Sha256::digest(&amino);
+        let id = multisig.id("cosmos").unwrap();
+        assert_eq!(id.to_bytes(), hash[..20].to_vec());
+        // key order is part of the key
+        let reversed = MultisigAccount::new(2, keys.into_iter().rev().collect()).unwrap();
+        assert_ne!(reversed.id("cosmos").unwrap(), id);
+    }
+
+    #[test]
+    fn validates_threshold() {
+        let keys: Vec<PublicKey> = members(2).iter().map(|m| m.public_key()).collect();
+
+        assert!(MultisigAccount::new(0, keys.clone()).is_err());
+        assert!(MultisigAccount::new(3, keys.clone()).is_err());
+        assert!(MultisigAccount::new(2, keys).is_ok());
+    }
+
+    #[test]
+    fn signs_and_combines() {
+        let members = members(5);
+        let keys: Vec<PublicKey> = members.iter().map(|m| m.public_key()).collect();
+        let multisig = MultisigAccount::new_sorted(3, keys).unwrap();
+        let signers = [&members[4], &members[0], &members[2]];
+        let unsigned = unsigned_tx(&multisig, &signers);
+        let partials: Vec<PartialSignature> = signers
+            .iter()
+            .map(|s| unsigned.sign_partial(s).unwrap())
+            .collect();
+
+        let json = partials[0].to_json().unwrap();
+        assert_eq!(PartialSignature::from_json(&json).unwrap(), partials[0]);
+
+        let signed = multisig.combine(&unsigned, &partials).unwrap();
+        let multi_signature = MultiSignature::decode(&signed.signatures[0][..]).unwrap();
+        assert_eq!(signed.signatures.len(), 1);
+        assert_eq!(multi_signature.signatures.len(), 3);
+
+        // below threshold
+        assert!(multisig.combine(&unsigned, &partials[..2]).is_err());
+        // a member that wasn't part of the signer set
+        let mut wrong_signers = partials[..2].to_vec();
+        wrong_signers.push(unsigned.sign_partial(&members[1]).unwrap());
+        assert!(multisig.combine(&unsigned, &wrong_signers).is_err());
+        // a signature over a different tx
+        let mut bad_signature = partials.clone();
+        bad_signature[0] = unsigned_tx(&multisig, &[&members[0], &members[2], &members[4]])
+            .sign_partial(signers[0])
+            .unwrap();
+        assert!(multisig.combine(&unsigned, &bad_signature).is_err());
+    }
+}
@@ -1,20 +1,17 @@
-use std::{thread, time::Duration};
+use std::time::Duration;
 
-use cosmrs::{dev, rpc, Tx};
+use cosmrs::{dev, rpc};
 use ocular::{
     account::AccountInfo,
     chain::{
-        client::{
-            airdrop::write_payments_toml, cache::Cache, tx::BroadcastCommitResponse, ChainClient,
-        },
-        config::ChainClientConfig,
+        client::{airdrop::write_payments_toml, cache::Cache, ChainClient},
+        config::{ChainClientConfig, TimeoutHeight},
     },
     keyring::Keyring,
     tx::{Coin, Payment},
     Timestamp,
 };
 use rand::Rng;
-use rpc::HttpClient;
 
 use crate::utils::{
     generate_accounts, run_single_node_test, ACCOUNT_PREFIX, CHAIN_ID, DENOM,
@@ -23,6 +20,8 @@ use crate::utils::{
 
 mod utils;
 
+const TX_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[test]
 #[ignore]
 fn airdrop_direct_single_sender_single_denom() {
@@ -33,14 +32,14 @@ fn airdrop_direct_single_sender_single_denom() {
             // by brute force, found that ~7400 recipients results in a 1MB transaction
             let recipients = generate_accounts(250);
             let payments = generate_payments(&recipients);
-            let total_to_distribute: u64 = payments.iter().map(|p| p.amount).sum();
+            let total_to_distribute: u128 = payments.iter().map(|p| p.amount).sum();
             let mut chain_client = init_test_chain_client().await;
             let mut txm = chain_client.get_basic_tx_metadata().await.unwrap();
             txm.gas_limit =
                 MULTISEND_BASE_GAS_APPROX + (PAYMENT_GAS_APPROX * payments.len() as u64);
 
             let sender_address = sender_account.address(ACCOUNT_PREFIX).unwrap();
-            let sender_starting_balance: u64 = chain_client
+            let sender_starting_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -57,9 +56,12 @@ fn airdrop_direct_single_sender_single_denom() {
                 .unwrap();
 
             // wait 1 minute for the tx to be included in a block
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let sender_ending_balance: u64 = chain_client
+            let sender_ending_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -146,7 +148,10 @@ fn airdrop_delegated_single_sender_single_denom() {
                 .await
                 .unwrap();
 
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
             // query *and* verify methods, just so both get exercised
             let _response = chain_client
@@ -180,10 +185,13 @@ fn airdrop_delegated_single_sender_single_denom() {
                 .await
                 .unwrap();
 
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let total_to_distribute: u64 = payments.iter().map(|p| p.amount).sum();
-            let sender_starting_balance: u64 = chain_client
+            let total_to_distribute: u128 = payments.iter().map(|p| p.amount).sum();
+            let sender_starting_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -202,9 +210,12 @@ fn airdrop_delegated_single_sender_single_denom() {
                 .unwrap();
 
             // wait 1 minute for the tx to be included in a block
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let sender_ending_balance: u64 = chain_client
+            let sender_ending_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -247,7 +258,7 @@ fn airdrop_toml_direct_single_sender_single_denom() {
 
             let recipients = generate_accounts(3);
             let payments = generate_payments(&recipients);
-            let total_to_distribute: u64 = payments.iter().map(|p| p.amount).sum();
+            let total_to_distribute: u128 = payments.iter().map(|p| p.amount).sum();
             let mut chain_client = init_test_chain_client().await;
             let mut txm = chain_client.get_basic_tx_metadata().await.unwrap();
 
@@ -269,7 +280,7 @@ fn airdrop_toml_direct_single_sender_single_denom() {
                 .send(&genesis_account, &sender_address, amount, Some(txm.clone()))
                 .await
                 .unwrap();
-            let sender_starting_balance: u64 = chain_client
+            let sender_starting_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -287,9 +298,12 @@ fn airdrop_toml_direct_single_sender_single_denom() {
                 .unwrap();
 
             // wait 1 minute for the tx to be included in a block
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let sender_ending_balance: u64 = chain_client
+            let sender_ending_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -332,7 +346,7 @@ fn airdrop_toml_delegated_single_sender_single_denom() {
 
             let recipients = generate_accounts(3);
             let payments = generate_payments(&recipients);
-            let total_to_distribute: u64 = payments.iter().map(|p| p.amount).sum();
+            let total_to_distribute: u128 = payments.iter().map(|p| p.amount).sum();
             let mut chain_client = init_test_chain_client().await;
             let mut txm = chain_client.get_basic_tx_metadata().await.unwrap();
 
@@ -404,9 +418,12 @@ fn airdrop_toml_delegated_single_sender_single_denom() {
                 .await
                 .unwrap();
 
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let sender_starting_balance: u64 = chain_client
+            let sender_starting_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -435,7 +452,10 @@ fn airdrop_toml_delegated_single_sender_single_denom() {
                 .await
                 .unwrap();
 
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
             // query *and* verify methods, just so both get exercised
             let _response = chain_client
@@ -462,9 +482,12 @@ fn airdrop_toml_delegated_single_sender_single_denom() {
                 .unwrap();
 
             // wait 1 minute for the tx to be included in a block
-            wait_for_tx(&chain_client.rpc_client, &response, 10).await;
+            chain_client
+                .wait_for_tx(response.hash, TX_TIMEOUT)
+                .await
+                .unwrap();
 
-            let sender_ending_balance: u64 = chain_client
+            let sender_ending_balance: u128 = chain_client
                 .query_all_balances(&sender_address)
                 .await
                 .unwrap()[0]
@@ -505,47 +528,17 @@ async fn init_test_chain_client() -> ChainClient {
         .add_item(grpc_address.clone(), 0)
         .unwrap();
 
-    ChainClient {
-        config: ChainClientConfig {
-            chain_name: "cosmrs".to_string(),
-            chain_id: CHAIN_ID.to_string(),
-            rpc_address: rpc_address.clone(),
-            grpc_address,
-            account_prefix: ACCOUNT_PREFIX.to_string(),
-            gas_adjustment: 1.2,
-            default_fee: ocular::tx::Coin {
-                amount: 0u64,
-                denom: DENOM.to_string(),
-            },
-        },
-        keyring: Keyring::new_file_store(None).expect("Could not create keyring."),
-        rpc_client: rpc_client.clone(),
-        cache: Some(cache),
-        connection_retry_attempts: 0,
-    }
-}
-
-async fn wait_for_tx(client: &HttpClient, res: &BroadcastCommitResponse, retries: u64) {
-    if res.check_tx.code.is_err() {
-        panic!("CheckTx error: {:?}", res);
-    }
-
-    if res.deliver_tx.code.is_err() {
-        panic!("DeliverTx error: {:?}", res);
-    }
-
-    let mut result_tx: Option<Tx> = None;
-    for _ in 0..retries {
-        if let Ok(tx) = Tx::find_by_hash(client, res.hash).await {
-            result_tx = Some(tx);
-        }
-
-        if result_tx.is_some() {
-            return;
-        }
-
-        thread::sleep(Duration::from_secs(6));
-    }
-
-    panic!("timed out waiting for transaction {}", res.hash);
+    let config = ChainClientConfig {
+        chain_name: "cosmrs".to_string(),
+        chain_id: CHAIN_ID.to_string(),
+        rpc_address: rpc_address.clone(),
+        grpc_address,
+        account_prefix: ACCOUNT_PREFIX.to_string(),
+        gas_adjustment: 1.2,
+        gas_prices: vec![format!("0{}", DENOM).parse().unwrap()],
+        timeout_height: TimeoutHeight::default(),
+    };
+    let keyring = Keyring::new_file_store(None).expect("Could not create keyring.");
+
+    ChainClient::new(config, keyring, Some(cache), 0).expect("Could not create chain client.")
 }
@@ -3,11 +3,10 @@ use ocular::{
     chain::{
         self,
         client::{cache::Cache, ChainClient, ChainClientBuilder},
-        config::ChainClientConfig,
+        config::{ChainClientConfig, TimeoutHeight},
         SOMMELIER,
     },
     keyring::Keyring,
-    tx::Coin,
 };
 
 #[assay]
@@ -22,12 +21,10 @@ async fn manual_chain_client_path() {
         chain_name: "cosmoshub".to_string(),
         chain_id: "cosmoshub-4".to_string(),
         gas_adjustment: 1.0,
+        gas_prices: vec!["0uatom".parse().unwrap()],
+        timeout_height: TimeoutHeight::default(),
         grpc_address: "https://cosmoshub.strange.love:9090".to_string(),
         rpc_address: "https://cosmoshub-4.technofractal.com:443".to_string(),
-        default_fee: Coin {
-            amount: 0,
-            denom: "uatom".to_string(),
-        },
     };
     let keyring = Keyring::try_default().unwrap();
     let cache = Some(Cache::create_memory_cache(None, 5).unwrap());
@@ -3,7 +3,10 @@
 // Requies docker
 use ocular::{
     account::AccountInfo,
-    chain::{client::cache::Cache, config::ChainClientConfig},
+    chain::{
+        client::cache::Cache,
+        config::{ChainClientConfig, TimeoutHeight},
+    },
     cosmos_modules::*,
     keyring::Keyring,
     tx::{Any, MultiSendIo, TxMetadata},
@@ -249,34 +252,33 @@ fn local_single_node_chain_test() {
                 .grpc_endpoint_cache
                 .add_item(grpc_address.clone(), 0)
                 .unwrap();
-            let mut chain_client = ChainClient {
-                config: ChainClientConfig {
-                    chain_name: "cosmrs".to_string(),
-                    chain_id: chain_id.to_string(),
-                    rpc_address: rpc_address.clone(),
-                    grpc_address,
-                    account_prefix: ACCOUNT_PREFIX.to_string(),
-                    gas_adjustment: 1.2,
-                    default_fee: ocular::tx::Coin {
-                        amount: default_fee_amount.into(),
-                        denom: DENOM.to_string(),
-                    },
-                },
-                keyring: Keyring::new_file_store(None).expect("Could not create keyring."),
-                rpc_client: rpc_client.clone(),
-                cache: Some(cache),
-                connection_retry_attempts: 0,
+            let config = ChainClientConfig {
+                chain_name: "cosmrs".to_string(),
+                chain_id: chain_id.to_string(),
+                rpc_address: rpc_address.clone(),
+                grpc_address,
+                account_prefix: ACCOUNT_PREFIX.to_string(),
+                gas_adjustment: 1.2,
+                gas_prices: vec![format!("{}{}", default_fee_amount, DENOM).parse().unwrap()],
+                timeout_height: TimeoutHeight::default(),
             };
+            let keyring = Keyring::new_file_store(None).expect("Could not create keyring.");
+            let mut chain_client = ChainClient::new(config, keyring, Some(cache), 0)
+                .expect("Could not create chain client.");
 
             dev::poll_for_first_block(&rpc_client).await;
 
             let tx_metadata = TxMetadata {
-                fee: chain_client.config.default_fee.clone(),
+                fee: vec![ocular::tx::Coin {
+                    amount: default_fee_amount.into(),
+                    denom: DENOM.to_string(),
+                }],
                 fee_payer: None,
                 fee_granter: None,
                 gas_limit: gas,
                 timeout_height: timeout_height.into(),
                 memo: MEMO.to_string(),
+                ..Default::default()
             };
 
             // Test MsgSend functionality
@@ -316,14 +318,24 @@ fn local_single_node_chain_test() {
                 .await
                 .expect("Could not broadcast msg.");
 
-            if actual_msg_grant_commit_response.check_tx.code.is_err() {
+            if actual_msg_grant_commit_response
+                .check_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "check_tx for msg_grant failed: {:?}",
                     actual_msg_grant_commit_response.check_tx
                 );
             }
 
-            if actual_msg_grant_commit_response.deliver_tx.code.is_err() {
+            if actual_msg_grant_commit_response
+                .deliver_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "deliver_tx for msg_grant failed: {:?}",
                     actual_msg_grant_commit_response.deliver_tx
@@ -354,14 +366,24 @@ fn local_single_node_chain_test() {
                 .await
                 .expect("Could not broadcast msg.");
 
-            if actual_msg_exec_commit_response.check_tx.code.is_err() {
+            if actual_msg_exec_commit_response
+                .check_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "check_tx for msg_exec failed: {:?}",
                     actual_msg_exec_commit_response.check_tx
                 );
             }
 
-            if actual_msg_exec_commit_response.deliver_tx.code.is_err() {
+            if actual_msg_exec_commit_response
+                .deliver_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "deliver_tx for msg_exec failed: {:?}",
                     actual_msg_exec_commit_response.deliver_tx
@@ -384,14 +406,24 @@ fn local_single_node_chain_test() {
                 .await
                 .expect("Could not broadcast msg.");
 
-            if actual_msg_revoke_commit_response.check_tx.code.is_err() {
+            if actual_msg_revoke_commit_response
+                .check_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "check_tx for msg_revoke failed: {:?}",
                     actual_msg_revoke_commit_response.check_tx
                 );
             }
 
-            if actual_msg_revoke_commit_response.deliver_tx.code.is_err() {
+            if actual_msg_revoke_commit_response
+                .deliver_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "deliver_tx for msg_revoke failed: {:?}",
                     actual_msg_revoke_commit_response.deliver_tx
@@ -406,6 +438,7 @@ fn local_single_node_chain_test() {
             // Test MsgExec does not work after permissions revoked
             let mut tx_metadata_memoed = tx_metadata.clone();
             tx_metadata_memoed.memo = String::from("Exec tx #2");
+            tx_metadata_memoed.allow_failed_tx = true;
 
             let mut msgs_to_send: Vec<Any> = Vec::new();
             msgs_to_send.push(
@@ -423,7 +456,12 @@ fn local_single_node_chain_test() {
                 .await
                 .expect("Could not broadcast msg.");
 
-            if actual_msg_exec_commit_response.check_tx.code.is_err() {
+            if actual_msg_exec_commit_response
+                .check_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "check_tx for msg_exec failed: {:?}",
                     actual_msg_exec_commit_response.check_tx
@@ -431,7 +469,7 @@ fn local_single_node_chain_test() {
             }
 
             // Assert permission error since acct delegation permission was revoked
-            assert_eq!(&actual_msg_exec_commit_response.deliver_tx.log.to_string()[..82], "failed to execute message; message index: 0: authorization not found: unauthorized");
+            assert_eq!(&actual_msg_exec_commit_response.deliver_tx.unwrap().log[..82], "failed to execute message; message index: 0: authorization not found: unauthorized");
 
             // Test MsgMultiSend functionality
             let actual_tx_commit_response = chain_client
@@ -439,13 +477,23 @@ fn local_single_node_chain_test() {
                 .await
                 .expect("Could not broadcast msg");
 
-            if actual_tx_commit_response.check_tx.code.is_err() {
+            if actual_tx_commit_response
+                .check_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "check_tx for msgsend failed: {:?}",
                     actual_tx_commit_response.check_tx
                 );
             }
-            if actual_tx_commit_response.deliver_tx.code.is_err() {
+            if actual_tx_commit_response
+                .deliver_tx
+                .as_ref()
+                .unwrap()
+                .is_err()
+            {
                 panic!(
                     "deliver_tx for msgsend failed: {:?}",
                     actual_tx_commit_response.deliver_tx